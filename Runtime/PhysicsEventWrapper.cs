@@ -649,7 +649,6 @@ public class RapierLoop
 						stay.Invoke(collider1);
 				}
 			}
-			RapierBindings.FreeCollisionEvents(eventsPtrToArray);
 		}
 
 		// Update GameObject positions of GameObjects with RigidBody component
@@ -35,13 +35,27 @@ namespace Packages.rapier4unity.Runtime
 		public ColliderHandle collider1;
 		public ColliderHandle collider2;
 		public bool is_started;
+		// Range of this event's points in GetCollisionContacts, empty for stopped events and sensors
+		public uint contacts_start;
+		public uint contacts_count;
+		// Parent bodies and user ids of both colliders, still set when a collider was removed with its body
+		public RigidBodyHandle body1;
+		public RigidBodyHandle body2;
+		public ulong user_id1;
+		public ulong user_id2;
+		// At least one of the colliders is a sensor, so this is a trigger event
+		public bool is_sensor;
+		// The pair stopped touching because one of the colliders was removed
+		public bool removed;
 	}
 
+	[StructLayout(LayoutKind.Sequential)]
 	public struct RawArray<T> where T : unmanaged
 	{
 		public IntPtr data;
-		public int length;
-		public int capacity;
+		// usize on the Rust side
+		public nint length;
+		public nint capacity;
 
 		public T this[int index]
 		{
@@ -92,6 +106,31 @@ namespace Packages.rapier4unity.Runtime
 		public override string ToString() => $"Rotation: {rotation}, Position: {position}";
 	}
 
+	[StructLayout(LayoutKind.Sequential)]
+	public struct RapierTransformPair
+	{
+		public RapierTransform previous;
+		public RapierTransform current;
+	}
+
+	// Header of the transform stream, `front_index` and `frame` are written by Rust after every step
+	[StructLayout(LayoutKind.Sequential)]
+	public struct TransformStreamHeader
+	{
+		public IntPtr buffer0;
+		public IntPtr buffer1;
+		public nuint capacity;
+		public uint front_index;
+		public ulong frame;
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct FixedStepInfo
+	{
+		public uint steps;
+		public float alpha;
+	}
+
 	public struct ImpulseJointHandle
 	{
 		public uint index;
@@ -99,4 +138,308 @@ namespace Packages.rapier4unity.Runtime
 
 		public override string ToString() => $"Index: {index}, Generation: {generation}";
 	}
+
+	// Operation codes of the command stream, keep in sync with CommandOp in commands.rs
+	public enum CommandOp : uint
+	{
+		SetLinearVelocity = 0,
+		SetAngularVelocity = 1,
+		AddForce = 2,
+		AddTorque = 3,
+		SetTransformPosition = 4,
+		SetTransformRotation = 5,
+		SetTransform = 6,
+		RemoveRigidBody = 7,
+		WakeUp = 8,
+		Sleep = 9,
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public unsafe struct RapierCommand
+	{
+		public uint op;
+		public uint arg;
+		public RigidBodyHandle handle;
+		public fixed float payload[7];
+	}
+
+	public enum CommandResult : uint
+	{
+		Ok = 0,
+		UnknownOp = 1,
+		InvalidHandle = 2,
+		InvalidArgument = 3,
+	}
+
+	// Descriptors start with the version they were written against, get one from the matching
+	// Default*Descriptor binding to start from the latest version and its defaults.
+	// Enums are 32-bit and bool fields are uints that are true when non-zero.
+
+	public enum ColliderShape : uint
+	{
+		Cuboid = 0,
+		Sphere = 1,
+		Capsule = 2,
+		Mesh = 3,
+		ConvexMesh = 4,
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct ColliderDescriptor
+	{
+		public uint version;
+		public ColliderShape shape;
+		public float3 half_extents;
+		public float radius;
+		public float half_height;
+		public IntPtr vertices_ptr;
+		public nuint vertices_count;
+		public IntPtr indices_ptr;
+		public nuint indices_count;
+		public float3 position;
+		public quaternion rotation;
+		public float density;
+		public float friction;
+		public float restitution;
+		public uint is_sensor;
+		// Version 2
+		public float contact_force_event_threshold;
+		// Version 3
+		public ulong user_id;
+		// Version 4
+		public uint active_events;
+		public uint active_collision_types;
+		// Version 5
+		public uint active_hooks;
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct RigidBodyDescriptor
+	{
+		public uint version;
+		public RigidBodyType body_type;
+		public ColliderHandle collider;
+		public float3 position;
+		public quaternion rotation;
+		public float3 linear_velocity;
+		public float3 angular_velocity;
+		public uint constraints;
+		public float linear_drag;
+		public float angular_drag;
+		public float gravity_scale;
+		public uint enable_ccd;
+		public uint can_sleep;
+	}
+
+	public enum JointType : uint
+	{
+		Fixed = 0,
+		Spherical = 1,
+		Revolute = 2,
+		Prismatic = 3,
+		Spring = 4,
+		Rope = 5,
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct JointDescriptor
+	{
+		public uint version;
+		public JointType joint_type;
+		public RigidBodyHandle body1;
+		public RigidBodyHandle body2;
+		public float3 local_anchor1;
+		public float3 local_anchor2;
+		public float3 axis;
+		public uint limits_enabled;
+		public float limit_min;
+		public float limit_max;
+		public uint contacts_enabled;
+		// Version 2
+		public float rest_length;
+		public float stiffness;
+		public float damping;
+		// Version 3
+		public uint use_local_frames;
+		public quaternion local_rotation1;
+		public quaternion local_rotation2;
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct IntegrationParametersDescriptor
+	{
+		public uint version;
+		public float dt;
+		public nuint solver_iterations;
+		public nuint solver_pgs_iterations;
+		public nuint solver_additional_friction_iterations;
+		public nuint solver_stabilization_iterations;
+		public nuint ccd_substeps;
+		public float contact_damping_ratio;
+		public float joint_damping_ratio;
+		public float contact_frequency;
+		public float joint_frequency;
+		public float prediction_distance;
+		public float max_corrective_velocity;
+		public float length_unit;
+	}
+
+	// A degree of freedom of a joint in its local frame
+	public enum JointAxis : uint
+	{
+		LinX = 0,
+		LinY = 1,
+		LinZ = 2,
+		AngX = 3,
+		AngY = 4,
+		AngZ = 5,
+	}
+
+	public enum MotorModel : uint
+	{
+		AccelerationBased = 0,
+		ForceBased = 1,
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct JointMotorDescriptor
+	{
+		public float target_pos;
+		public float target_vel;
+		public float stiffness;
+		public float damping;
+		public float max_force;
+		public MotorModel model;
+	}
+
+	// Axis masks use bit 0..5 for LinX, LinY, LinZ, AngX, AngY, AngZ of the joint frame
+	[StructLayout(LayoutKind.Sequential)]
+	public unsafe struct GenericJointDescriptor
+	{
+		public uint version;
+		public RigidBodyHandle body1;
+		public RigidBodyHandle body2;
+		public float3 local_frame1_position;
+		public quaternion local_frame1_rotation;
+		public float3 local_frame2_position;
+		public quaternion local_frame2_rotation;
+		public uint locked_axes;
+		public uint limit_axes;
+		public uint motor_axes;
+		public uint coupled_axes;
+		// Indexed by axis, in radians for angular axes
+		public fixed float limits_min[6];
+		public fixed float limits_max[6];
+		public JointMotorDescriptor motor_lin_x;
+		public JointMotorDescriptor motor_lin_y;
+		public JointMotorDescriptor motor_lin_z;
+		public JointMotorDescriptor motor_ang_x;
+		public JointMotorDescriptor motor_ang_y;
+		public JointMotorDescriptor motor_ang_z;
+		public uint contacts_enabled;
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct JointBrokenEvent
+	{
+		public ImpulseJointHandle joint;
+		public RigidBodyHandle body1;
+		public RigidBodyHandle body2;
+		// Force and torque the joint applied during the step it broke
+		public float force;
+		public float torque;
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct JointState
+	{
+		// Position of the second frame relative to the first, along LinX, LinY and LinZ
+		public float3 translation;
+		// Rotation of the second frame relative to the first around AngX, AngY and AngZ, in radians
+		public float3 angles;
+		// Relative velocities, in the axes of the first frame
+		public float3 linear_velocity;
+		public float3 angular_velocity;
+		// Applied to the second body during the last step in world space, the first body gets the opposite.
+		// Ropes and springs pull along the line between the anchors, but Rapier reports that on the first frame's X axis
+		public float3 force;
+		public float3 torque;
+	}
+
+	public struct MultibodyJointHandle
+	{
+		public uint index;
+		public uint generation;
+
+		public override string ToString() => $"Index: {index}, Generation: {generation}";
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct InverseKinematicsDescriptor
+	{
+		public uint version;
+		// Higher values converge slower but overshoot less
+		public float damping;
+		public uint max_iterations;
+		// Axes of the target pose to reach, bits 0 to 5 are LinX, LinY, LinZ, AngX, AngY and AngZ
+		public uint constrained_axes;
+		// Stop iterating once both errors are below these
+		public float epsilon_linear;
+		public float epsilon_angular;
+	}
+
+	// What SolveMultibodyIk does with the displacements it computed, passed as its mode
+	public enum InverseKinematicsMode : uint
+	{
+		Displacements = 0,
+		ApplyPose = 1,
+		MotorTargets = 2,
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct ContactPoint
+	{
+		// World-space point on the event's first collider
+		public float3 point;
+		// World-space normal pointing from the first collider towards the second
+		public float3 normal;
+		// Positive when the colliders overlap, negative when the point is only predicted
+		public float depth;
+		// Normal impulse the point applied during the last step
+		public float impulse;
+	}
+
+	[StructLayout(LayoutKind.Sequential)]
+	public struct ContactForceEvent
+	{
+		public ColliderHandle collider1;
+		public ColliderHandle collider2;
+		// Sum of the forces at every contact point, and the sum of their magnitudes
+		public float3 total_force;
+		public float total_force_magnitude;
+		// Strongest force at a single contact point, the direction is a world-space unit vector
+		public float3 max_force_direction;
+		public float max_force_magnitude;
+	}
+
+	// What a contact pair filter callback returns, keep in sync with ContactPairFilterResult in hooks.rs
+	public enum ContactPairFilterResult : uint
+	{
+		Ignore = 0,
+		ComputeImpulses = 1,
+		ContactsOnly = 2,
+	}
+
+	// Contact handed to the solver contact modifier callback, which can edit it in place
+	[StructLayout(LayoutKind.Sequential)]
+	public struct SolverContact
+	{
+		public float3 point;
+		public float dist;
+		public float friction;
+		public float restitution;
+		public float3 tangent_velocity;
+		// Set to 0 to drop the contact
+		public uint enabled;
+	}
 }
\ No newline at end of file
@@ -29,12 +29,141 @@ internal static unsafe class RapierBindings
 #endif
 #endif
 	private const CallingConvention Convention = CallingConvention.Cdecl;
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static UIntPtr ExecuteCommands(RapierCommand* commandsPtr, UIntPtr count, CommandResult* outResults) => ((delegate* unmanaged[Cdecl]<RapierCommand*, UIntPtr, CommandResult*, UIntPtr>) data.Data.executeCommands)(commandsPtr, count, outResults);
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="execute_commands")]
+	public static extern unsafe UIntPtr ExecuteCommands(RapierCommand* commandsPtr, UIntPtr count, CommandResult* outResults);
+#endif
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static RapierTransformPair GetTransformPair(RigidBodyHandle rbHandle) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, RapierTransformPair>) data.Data.getTransformPair)(rbHandle);
+	public static RapierTransform GetInterpolatedTransform(RigidBodyHandle rbHandle, float alpha) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, float, RapierTransform>) data.Data.getInterpolatedTransform)(rbHandle, alpha);
+	public static FixedStepInfo AdvanceTimeAccumulator(float frameDt, uint maxSteps) => ((delegate* unmanaged[Cdecl]<float, uint, FixedStepInfo>) data.Data.advanceTimeAccumulator)(frameDt, maxSteps);
+	public static void ResetTimeAccumulator() => ((delegate* unmanaged[Cdecl]<void>) data.Data.resetTimeAccumulator)();
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_transform_pair")]
+	public static extern unsafe RapierTransformPair GetTransformPair(RigidBodyHandle rbHandle);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_interpolated_transform")]
+	public static extern unsafe RapierTransform GetInterpolatedTransform(RigidBodyHandle rbHandle, float alpha);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="advance_time_accumulator")]
+	public static extern unsafe FixedStepInfo AdvanceTimeAccumulator(float frameDt, uint maxSteps);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="reset_time_accumulator")]
+	public static extern unsafe void ResetTimeAccumulator();
+#endif
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static void SetJointMotor(ImpulseJointHandle handle, uint axis, float targetPos, float targetVel, float stiffness, float damping, float maxForce, uint model) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, uint, float, float, float, float, float, uint, void>) data.Data.setJointMotor)(handle, axis, targetPos, targetVel, stiffness, damping, maxForce, model);
+	public static void DisableJointMotor(ImpulseJointHandle handle, uint axis) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, uint, void>) data.Data.disableJointMotor)(handle, axis);
+	public static void SetJointLimits(ImpulseJointHandle handle, uint axis, float min, float max) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, uint, float, float, void>) data.Data.setJointLimits)(handle, axis, min, max);
+	public static void DisableJointLimits(ImpulseJointHandle handle, uint axis) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, uint, void>) data.Data.disableJointLimits)(handle, axis);
+	public static void SetTwistSwingLimits(ImpulseJointHandle handle, float twistMin, float twistMax, float swing1Limit, float swing2Limit) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, float, float, float, float, void>) data.Data.setTwistSwingLimits)(handle, twistMin, twistMax, swing1Limit, swing2Limit);
+	public static void SetJointAnchors(ImpulseJointHandle handle, float3 localAnchor1, float3 localAnchor2) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, float3, float3, void>) data.Data.setJointAnchors)(handle, localAnchor1, localAnchor2);
+	public static void SetJointAxes(ImpulseJointHandle handle, float3 localAxis1, float3 localAxis2) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, float3, float3, void>) data.Data.setJointAxes)(handle, localAxis1, localAxis2);
+	public static void SetJointFrames(ImpulseJointHandle handle, float3 localPosition1, quaternion localRotation1, float3 localPosition2, quaternion localRotation2) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, float3, quaternion, float3, quaternion, void>) data.Data.setJointFrames)(handle, localPosition1, localRotation1, localPosition2, localRotation2);
+	public static void SetJointContactsEnabled(ImpulseJointHandle handle, bool enabled) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, bool, void>) data.Data.setJointContactsEnabled)(handle, enabled);
+	public static void SetJointEnabled(ImpulseJointHandle handle, bool enabled) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, bool, void>) data.Data.setJointEnabled)(handle, enabled);
+	public static ImpulseJointHandle SetJointBodies(ImpulseJointHandle handle, RigidBodyHandle body1, RigidBodyHandle body2) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, RigidBodyHandle, RigidBodyHandle, ImpulseJointHandle>) data.Data.setJointBodies)(handle, body1, body2);
+	public static void SetJointBreakThreshold(ImpulseJointHandle handle, float breakForce, float breakTorque) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, float, float, void>) data.Data.setJointBreakThreshold)(handle, breakForce, breakTorque);
+	public static RawArray<JointBrokenEvent> GetBrokenJoints() => ((delegate* unmanaged[Cdecl]<RawArray<JointBrokenEvent>>) data.Data.getBrokenJoints)();
+	public static bool GetJointState(ImpulseJointHandle handle, JointState* outState) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, JointState*, bool>) data.Data.getJointState)(handle, outState);
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_motor")]
+	public static extern unsafe void SetJointMotor(ImpulseJointHandle handle, uint axis, float targetPos, float targetVel, float stiffness, float damping, float maxForce, uint model);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="disable_joint_motor")]
+	public static extern unsafe void DisableJointMotor(ImpulseJointHandle handle, uint axis);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_limits")]
+	public static extern unsafe void SetJointLimits(ImpulseJointHandle handle, uint axis, float min, float max);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="disable_joint_limits")]
+	public static extern unsafe void DisableJointLimits(ImpulseJointHandle handle, uint axis);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_twist_swing_limits")]
+	public static extern unsafe void SetTwistSwingLimits(ImpulseJointHandle handle, float twistMin, float twistMax, float swing1Limit, float swing2Limit);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_anchors")]
+	public static extern unsafe void SetJointAnchors(ImpulseJointHandle handle, float3 localAnchor1, float3 localAnchor2);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_axes")]
+	public static extern unsafe void SetJointAxes(ImpulseJointHandle handle, float3 localAxis1, float3 localAxis2);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_frames")]
+	public static extern unsafe void SetJointFrames(ImpulseJointHandle handle, float3 localPosition1, quaternion localRotation1, float3 localPosition2, quaternion localRotation2);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_contacts_enabled")]
+	public static extern unsafe void SetJointContactsEnabled(ImpulseJointHandle handle, bool enabled);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_enabled")]
+	public static extern unsafe void SetJointEnabled(ImpulseJointHandle handle, bool enabled);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_bodies")]
+	public static extern unsafe ImpulseJointHandle SetJointBodies(ImpulseJointHandle handle, RigidBodyHandle body1, RigidBodyHandle body2);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_joint_break_threshold")]
+	public static extern unsafe void SetJointBreakThreshold(ImpulseJointHandle handle, float breakForce, float breakTorque);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_broken_joints")]
+	public static extern unsafe RawArray<JointBrokenEvent> GetBrokenJoints();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_joint_state")]
+	public static extern unsafe bool GetJointState(ImpulseJointHandle handle, JointState* outState);
+#endif
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static void GetTransforms(RigidBodyHandle* handlesPtr, UIntPtr handlesCount, RapierTransform* outTransforms, float3* outLinearVelocities, float3* outAngularVelocities) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle*, UIntPtr, RapierTransform*, float3*, float3*, void>) data.Data.getTransforms)(handlesPtr, handlesCount, outTransforms, outLinearVelocities, outAngularVelocities);
+	public static UIntPtr GetActiveBodyCount() => ((delegate* unmanaged[Cdecl]<UIntPtr>) data.Data.getActiveBodyCount)();
+	public static UIntPtr GetActiveTransforms(RigidBodyHandle* outHandles, RapierTransform* outTransforms, float3* outLinearVelocities, float3* outAngularVelocities, UIntPtr capacity) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle*, RapierTransform*, float3*, float3*, UIntPtr, UIntPtr>) data.Data.getActiveTransforms)(outHandles, outTransforms, outLinearVelocities, outAngularVelocities, capacity);
+	public static RawArray<RigidBodyHandle> GetMovedBodies() => ((delegate* unmanaged[Cdecl]<RawArray<RigidBodyHandle>>) data.Data.getMovedBodies)();
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_transforms")]
+	public static extern unsafe void GetTransforms(RigidBodyHandle* handlesPtr, UIntPtr handlesCount, RapierTransform* outTransforms, float3* outLinearVelocities, float3* outAngularVelocities);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_active_body_count")]
+	public static extern unsafe UIntPtr GetActiveBodyCount();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_active_transforms")]
+	public static extern unsafe UIntPtr GetActiveTransforms(RigidBodyHandle* outHandles, RapierTransform* outTransforms, float3* outLinearVelocities, float3* outAngularVelocities, UIntPtr capacity);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_moved_bodies")]
+	public static extern unsafe RawArray<RigidBodyHandle> GetMovedBodies();
+#endif
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static RawArray<ContactPoint> GetCollisionContacts() => ((delegate* unmanaged[Cdecl]<RawArray<ContactPoint>>) data.Data.getCollisionContacts)();
+	public static void SetColliderContactForceEventThreshold(ColliderHandle colliderHandle, float threshold) => ((delegate* unmanaged[Cdecl]<ColliderHandle, float, void>) data.Data.setColliderContactForceEventThreshold)(colliderHandle, threshold);
+	public static RawArray<ContactForceEvent> GetContactForceEvents() => ((delegate* unmanaged[Cdecl]<RawArray<ContactForceEvent>>) data.Data.getContactForceEvents)();
+	public static RawArray<CollisionEvent> GetCollisionStayEvents() => ((delegate* unmanaged[Cdecl]<RawArray<CollisionEvent>>) data.Data.getCollisionStayEvents)();
+	public static void SetStayEventContacts(bool enabled) => ((delegate* unmanaged[Cdecl]<bool, void>) data.Data.setStayEventContacts)(enabled);
+	public static void SetColliderStayEvents(ColliderHandle colliderHandle, bool enabled) => ((delegate* unmanaged[Cdecl]<ColliderHandle, bool, void>) data.Data.setColliderStayEvents)(colliderHandle, enabled);
+	public static void SetColliderUserId(ColliderHandle colliderHandle, ulong userId) => ((delegate* unmanaged[Cdecl]<ColliderHandle, ulong, void>) data.Data.setColliderUserId)(colliderHandle, userId);
+	public static void SetColliderActiveEvents(ColliderHandle colliderHandle, uint events) => ((delegate* unmanaged[Cdecl]<ColliderHandle, uint, void>) data.Data.setColliderActiveEvents)(colliderHandle, events);
+	public static void SetColliderActiveCollisionTypes(ColliderHandle colliderHandle, uint collisionTypes) => ((delegate* unmanaged[Cdecl]<ColliderHandle, uint, void>) data.Data.setColliderActiveCollisionTypes)(colliderHandle, collisionTypes);
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_collision_contacts")]
+	public static extern unsafe RawArray<ContactPoint> GetCollisionContacts();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_collider_contact_force_event_threshold")]
+	public static extern unsafe void SetColliderContactForceEventThreshold(ColliderHandle colliderHandle, float threshold);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_contact_force_events")]
+	public static extern unsafe RawArray<ContactForceEvent> GetContactForceEvents();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_collision_stay_events")]
+	public static extern unsafe RawArray<CollisionEvent> GetCollisionStayEvents();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_stay_event_contacts")]
+	public static extern unsafe void SetStayEventContacts(bool enabled);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_collider_stay_events")]
+	public static extern unsafe void SetColliderStayEvents(ColliderHandle colliderHandle, bool enabled);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_collider_user_id")]
+	public static extern unsafe void SetColliderUserId(ColliderHandle colliderHandle, ulong userId);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_collider_active_events")]
+	public static extern unsafe void SetColliderActiveEvents(ColliderHandle colliderHandle, uint events);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_collider_active_collision_types")]
+	public static extern unsafe void SetColliderActiveCollisionTypes(ColliderHandle colliderHandle, uint collisionTypes);
+#endif
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static void SetPhysicsHookCallbacks(IntPtr filterContactPair, IntPtr filterIntersectionPair, IntPtr modifySolverContacts) => ((delegate* unmanaged[Cdecl]<IntPtr, IntPtr, IntPtr, void>) data.Data.setPhysicsHookCallbacks)(filterContactPair, filterIntersectionPair, modifySolverContacts);
+	public static void SetColliderActiveHooks(ColliderHandle colliderHandle, uint hooks) => ((delegate* unmanaged[Cdecl]<ColliderHandle, uint, void>) data.Data.setColliderActiveHooks)(colliderHandle, hooks);
+	public static void SetColliderOneWayPlatform(ColliderHandle colliderHandle, float3 localNormal, float allowedAngle) => ((delegate* unmanaged[Cdecl]<ColliderHandle, float3, float, void>) data.Data.setColliderOneWayPlatform)(colliderHandle, localNormal, allowedAngle);
+	public static void SetColliderConveyor(ColliderHandle colliderHandle, float3 localVelocity) => ((delegate* unmanaged[Cdecl]<ColliderHandle, float3, void>) data.Data.setColliderConveyor)(colliderHandle, localVelocity);
+	public static void IgnoreCollisionPair(ColliderHandle collider1, ColliderHandle collider2, bool ignore) => ((delegate* unmanaged[Cdecl]<ColliderHandle, ColliderHandle, bool, void>) data.Data.ignoreCollisionPair)(collider1, collider2, ignore);
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_physics_hook_callbacks")]
+	public static extern unsafe void SetPhysicsHookCallbacks(IntPtr filterContactPair, IntPtr filterIntersectionPair, IntPtr modifySolverContacts);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_collider_active_hooks")]
+	public static extern unsafe void SetColliderActiveHooks(ColliderHandle colliderHandle, uint hooks);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_collider_one_way_platform")]
+	public static extern unsafe void SetColliderOneWayPlatform(ColliderHandle colliderHandle, float3 localNormal, float allowedAngle);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_collider_conveyor")]
+	public static extern unsafe void SetColliderConveyor(ColliderHandle colliderHandle, float3 localVelocity);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="ignore_collision_pair")]
+	public static extern unsafe void IgnoreCollisionPair(ColliderHandle collider1, ColliderHandle collider2, bool ignore);
+#endif
 #if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
 	public static void Init(FunctionsToCallFromRust* funcs) => ((delegate* unmanaged[Cdecl]<FunctionsToCallFromRust*, void>) data.Data.init)(funcs);
 	public static void HelloWorld() => ((delegate* unmanaged[Cdecl]<void>) data.Data.helloWorld)();
 	public static void Teardown() => ((delegate* unmanaged[Cdecl]<void>) data.Data.teardown)();
 	public static RawArray<CollisionEvent>* Solve() => ((delegate* unmanaged[Cdecl]<RawArray<CollisionEvent>*>) data.Data.solve)();
-	public static void FreeCollisionEvents(RawArray<CollisionEvent>* ptr) => ((delegate* unmanaged[Cdecl]<RawArray<CollisionEvent>*, void>) data.Data.freeCollisionEvents)(ptr);
+	public static void FreeCollisionEvents(RawArray<CollisionEvent>* Ptr) => ((delegate* unmanaged[Cdecl]<RawArray<CollisionEvent>*, void>) data.Data.freeCollisionEvents)(Ptr);
 	public static void SetGravity(float x, float y, float z) => ((delegate* unmanaged[Cdecl]<float, float, float, void>) data.Data.setGravity)(x, y, z);
 	public static void SetTimeStep(float dt) => ((delegate* unmanaged[Cdecl]<float, void>) data.Data.setTimeStep)(dt);
 	public static ColliderHandle AddCuboidCollider(float halfExtentsX, float halfExtentsY, float halfExtentsZ, float mass, bool isSensor) => ((delegate* unmanaged[Cdecl]<float, float, float, float, bool, ColliderHandle>) data.Data.addCuboidCollider)(halfExtentsX, halfExtentsY, halfExtentsZ, mass, isSensor);
@@ -49,6 +178,8 @@ internal static unsafe class RapierBindings
 	public static ImpulseJointHandle AddSphericalJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float localFrame1X, float localFrame1Y, float localFrame1Z, float localFrame2X, float localFrame2Y, float localFrame2Z, bool selfCollision) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, RigidBodyHandle, float, float, float, float, float, float, bool, ImpulseJointHandle>) data.Data.addSphericalJoint)(rb1Handle, rb2Handle, localFrame1X, localFrame1Y, localFrame1Z, localFrame2X, localFrame2Y, localFrame2Z, selfCollision);
 	public static ImpulseJointHandle AddRevoluteJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float axisX, float axisY, float axisZ, float localFrame1X, float localFrame1Y, float localFrame1Z, float localFrame2X, float localFrame2Y, float localFrame2Z, bool selfCollision) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, RigidBodyHandle, float, float, float, float, float, float, float, float, float, bool, ImpulseJointHandle>) data.Data.addRevoluteJoint)(rb1Handle, rb2Handle, axisX, axisY, axisZ, localFrame1X, localFrame1Y, localFrame1Z, localFrame2X, localFrame2Y, localFrame2Z, selfCollision);
 	public static ImpulseJointHandle AddPrismaticJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float axisX, float axisY, float axisZ, float localFrame1X, float localFrame1Y, float localFrame1Z, float localFrame2X, float localFrame2Y, float localFrame2Z, float limitMin, float limitMax, bool selfCollision) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, RigidBodyHandle, float, float, float, float, float, float, float, float, float, float, float, bool, ImpulseJointHandle>) data.Data.addPrismaticJoint)(rb1Handle, rb2Handle, axisX, axisY, axisZ, localFrame1X, localFrame1Y, localFrame1Z, localFrame2X, localFrame2Y, localFrame2Z, limitMin, limitMax, selfCollision);
+	public static ImpulseJointHandle AddSpringJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float localAnchor1X, float localAnchor1Y, float localAnchor1Z, float localAnchor2X, float localAnchor2Y, float localAnchor2Z, float restLength, float stiffness, float damping, float minLimit, float maxLimit, bool selfCollision) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, RigidBodyHandle, float, float, float, float, float, float, float, float, float, float, float, bool, ImpulseJointHandle>) data.Data.addSpringJoint)(rb1Handle, rb2Handle, localAnchor1X, localAnchor1Y, localAnchor1Z, localAnchor2X, localAnchor2Y, localAnchor2Z, restLength, stiffness, damping, minLimit, maxLimit, selfCollision);
+	public static ImpulseJointHandle AddRopeJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float localAnchor1X, float localAnchor1Y, float localAnchor1Z, float localAnchor2X, float localAnchor2Y, float localAnchor2Z, float maxDistance, bool selfCollision) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, RigidBodyHandle, float, float, float, float, float, float, float, bool, ImpulseJointHandle>) data.Data.addRopeJoint)(rb1Handle, rb2Handle, localAnchor1X, localAnchor1Y, localAnchor1Z, localAnchor2X, localAnchor2Y, localAnchor2Z, maxDistance, selfCollision);
 	public static void RemoveJoint(ImpulseJointHandle handle) => ((delegate* unmanaged[Cdecl]<ImpulseJointHandle, void>) data.Data.removeJoint)(handle);
 	public static RapierTransform GetTransform(RigidBodyHandle rbHandle) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, RapierTransform>) data.Data.getTransform)(rbHandle);
 	public static void SetTransformPosition(RigidBodyHandle rbHandle, float positionX, float positionY, float positionZ) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, float, float, float, void>) data.Data.setTransformPosition)(rbHandle, positionX, positionY, positionZ);
@@ -72,7 +203,7 @@ internal static unsafe class RapierBindings
 	[DllImport(DllName, CallingConvention = Convention, EntryPoint="solve")]
 	public static extern unsafe RawArray<CollisionEvent>* Solve();
 	[DllImport(DllName, CallingConvention = Convention, EntryPoint="free_collision_events")]
-	public static extern unsafe void FreeCollisionEvents(RawArray<CollisionEvent>* ptr);
+	public static extern unsafe void FreeCollisionEvents(RawArray<CollisionEvent>* Ptr);
 	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_gravity")]
 	public static extern unsafe void SetGravity(float x, float y, float z);
 	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_time_step")]
@@ -101,6 +232,10 @@ internal static unsafe class RapierBindings
 	public static extern unsafe ImpulseJointHandle AddRevoluteJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float axisX, float axisY, float axisZ, float localFrame1X, float localFrame1Y, float localFrame1Z, float localFrame2X, float localFrame2Y, float localFrame2Z, bool selfCollision);
 	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_prismatic_joint")]
 	public static extern unsafe ImpulseJointHandle AddPrismaticJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float axisX, float axisY, float axisZ, float localFrame1X, float localFrame1Y, float localFrame1Z, float localFrame2X, float localFrame2Y, float localFrame2Z, float limitMin, float limitMax, bool selfCollision);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_spring_joint")]
+	public static extern unsafe ImpulseJointHandle AddSpringJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float localAnchor1X, float localAnchor1Y, float localAnchor1Z, float localAnchor2X, float localAnchor2Y, float localAnchor2Z, float restLength, float stiffness, float damping, float minLimit, float maxLimit, bool selfCollision);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_rope_joint")]
+	public static extern unsafe ImpulseJointHandle AddRopeJoint(RigidBodyHandle rb1Handle, RigidBodyHandle rb2Handle, float localAnchor1X, float localAnchor1Y, float localAnchor1Z, float localAnchor2X, float localAnchor2Y, float localAnchor2Z, float maxDistance, bool selfCollision);
 	[DllImport(DllName, CallingConvention = Convention, EntryPoint="remove_joint")]
 	public static extern unsafe void RemoveJoint(ImpulseJointHandle handle);
 	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_transform")]
@@ -128,6 +263,90 @@ internal static unsafe class RapierBindings
 	[DllImport(DllName, CallingConvention = Convention, EntryPoint="cast_ray")]
 	public static extern unsafe bool CastRay(float fromX, float fromY, float fromZ, float dirX, float dirY, float dirZ, RapierRaycastHit* outHit);
 #endif
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static MultibodyJointHandle AddMultibodyJointFromDescriptor(JointDescriptor* descriptor) => ((delegate* unmanaged[Cdecl]<JointDescriptor*, MultibodyJointHandle>) data.Data.addMultibodyJointFromDescriptor)(descriptor);
+	public static MultibodyJointHandle AddMultibodyGenericJoint(GenericJointDescriptor* descriptor) => ((delegate* unmanaged[Cdecl]<GenericJointDescriptor*, MultibodyJointHandle>) data.Data.addMultibodyGenericJoint)(descriptor);
+	public static void RemoveMultibodyJoint(MultibodyJointHandle handle) => ((delegate* unmanaged[Cdecl]<MultibodyJointHandle, void>) data.Data.removeMultibodyJoint)(handle);
+	public static void SetMultibodyJointMotor(MultibodyJointHandle handle, uint axis, float targetPos, float targetVel, float stiffness, float damping, float maxForce, uint model) => ((delegate* unmanaged[Cdecl]<MultibodyJointHandle, uint, float, float, float, float, float, uint, void>) data.Data.setMultibodyJointMotor)(handle, axis, targetPos, targetVel, stiffness, damping, maxForce, model);
+	public static void DisableMultibodyJointMotor(MultibodyJointHandle handle, uint axis) => ((delegate* unmanaged[Cdecl]<MultibodyJointHandle, uint, void>) data.Data.disableMultibodyJointMotor)(handle, axis);
+	public static void SetMultibodyJointLimits(MultibodyJointHandle handle, uint axis, float min, float max) => ((delegate* unmanaged[Cdecl]<MultibodyJointHandle, uint, float, float, void>) data.Data.setMultibodyJointLimits)(handle, axis, min, max);
+	public static UIntPtr GetMultibodyJointDofCount(MultibodyJointHandle handle) => ((delegate* unmanaged[Cdecl]<MultibodyJointHandle, UIntPtr>) data.Data.getMultibodyJointDofCount)(handle);
+	public static UIntPtr GetMultibodyJointCoordinates(MultibodyJointHandle handle, float* outPositions, float* outVelocities, UIntPtr capacity) => ((delegate* unmanaged[Cdecl]<MultibodyJointHandle, float*, float*, UIntPtr, UIntPtr>) data.Data.getMultibodyJointCoordinates)(handle, outPositions, outVelocities, capacity);
+	public static InverseKinematicsDescriptor DefaultInverseKinematicsDescriptor() => ((delegate* unmanaged[Cdecl]<InverseKinematicsDescriptor>) data.Data.defaultInverseKinematicsDescriptor)();
+	public static UIntPtr SolveMultibodyIk(MultibodyJointHandle handle, float3 targetPosition, quaternion targetRotation, InverseKinematicsDescriptor* options, uint mode, float* outDisplacements, UIntPtr capacity) => ((delegate* unmanaged[Cdecl]<MultibodyJointHandle, float3, quaternion, InverseKinematicsDescriptor*, uint, float*, UIntPtr, UIntPtr>) data.Data.solveMultibodyIk)(handle, targetPosition, targetRotation, options, mode, outDisplacements, capacity);
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_multibody_joint_from_descriptor")]
+	public static extern unsafe MultibodyJointHandle AddMultibodyJointFromDescriptor(JointDescriptor* descriptor);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_multibody_generic_joint")]
+	public static extern unsafe MultibodyJointHandle AddMultibodyGenericJoint(GenericJointDescriptor* descriptor);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="remove_multibody_joint")]
+	public static extern unsafe void RemoveMultibodyJoint(MultibodyJointHandle handle);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_multibody_joint_motor")]
+	public static extern unsafe void SetMultibodyJointMotor(MultibodyJointHandle handle, uint axis, float targetPos, float targetVel, float stiffness, float damping, float maxForce, uint model);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="disable_multibody_joint_motor")]
+	public static extern unsafe void DisableMultibodyJointMotor(MultibodyJointHandle handle, uint axis);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_multibody_joint_limits")]
+	public static extern unsafe void SetMultibodyJointLimits(MultibodyJointHandle handle, uint axis, float min, float max);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_multibody_joint_dof_count")]
+	public static extern unsafe UIntPtr GetMultibodyJointDofCount(MultibodyJointHandle handle);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="get_multibody_joint_coordinates")]
+	public static extern unsafe UIntPtr GetMultibodyJointCoordinates(MultibodyJointHandle handle, float* outPositions, float* outVelocities, UIntPtr capacity);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="default_inverse_kinematics_descriptor")]
+	public static extern unsafe InverseKinematicsDescriptor DefaultInverseKinematicsDescriptor();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="solve_multibody_ik")]
+	public static extern unsafe UIntPtr SolveMultibodyIk(MultibodyJointHandle handle, float3 targetPosition, quaternion targetRotation, InverseKinematicsDescriptor* options, uint mode, float* outDisplacements, UIntPtr capacity);
+#endif
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static TransformStreamHeader* CreateTransformStream(UIntPtr capacity) => ((delegate* unmanaged[Cdecl]<UIntPtr, TransformStreamHeader*>) data.Data.createTransformStream)(capacity);
+	public static void DestroyTransformStream() => ((delegate* unmanaged[Cdecl]<void>) data.Data.destroyTransformStream)();
+	public static uint RegisterTransformStreamBody(RigidBodyHandle rbHandle) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, uint>) data.Data.registerTransformStreamBody)(rbHandle);
+	public static void UnregisterTransformStreamBody(RigidBodyHandle rbHandle) => ((delegate* unmanaged[Cdecl]<RigidBodyHandle, void>) data.Data.unregisterTransformStreamBody)(rbHandle);
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="create_transform_stream")]
+	public static extern unsafe TransformStreamHeader* CreateTransformStream(UIntPtr capacity);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="destroy_transform_stream")]
+	public static extern unsafe void DestroyTransformStream();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="register_transform_stream_body")]
+	public static extern unsafe uint RegisterTransformStreamBody(RigidBodyHandle rbHandle);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="unregister_transform_stream_body")]
+	public static extern unsafe void UnregisterTransformStreamBody(RigidBodyHandle rbHandle);
+#endif
+#if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
+	public static ColliderDescriptor DefaultColliderDescriptor() => ((delegate* unmanaged[Cdecl]<ColliderDescriptor>) data.Data.defaultColliderDescriptor)();
+	public static ColliderHandle AddColliderFromDescriptor(ColliderDescriptor* descriptor) => ((delegate* unmanaged[Cdecl]<ColliderDescriptor*, ColliderHandle>) data.Data.addColliderFromDescriptor)(descriptor);
+	public static RigidBodyDescriptor DefaultRigidBodyDescriptor() => ((delegate* unmanaged[Cdecl]<RigidBodyDescriptor>) data.Data.defaultRigidBodyDescriptor)();
+	public static RigidBodyHandle AddRigidBodyFromDescriptor(RigidBodyDescriptor* descriptor) => ((delegate* unmanaged[Cdecl]<RigidBodyDescriptor*, RigidBodyHandle>) data.Data.addRigidBodyFromDescriptor)(descriptor);
+	public static JointDescriptor DefaultJointDescriptor() => ((delegate* unmanaged[Cdecl]<JointDescriptor>) data.Data.defaultJointDescriptor)();
+	public static ImpulseJointHandle AddJointFromDescriptor(JointDescriptor* descriptor) => ((delegate* unmanaged[Cdecl]<JointDescriptor*, ImpulseJointHandle>) data.Data.addJointFromDescriptor)(descriptor);
+	public static JointDescriptor JointDescriptorFromWorldAnchor(uint jointType, RigidBodyHandle body1, RigidBodyHandle body2, float3 worldAnchor, float3 worldAxis) => ((delegate* unmanaged[Cdecl]<uint, RigidBodyHandle, RigidBodyHandle, float3, float3, JointDescriptor>) data.Data.jointDescriptorFromWorldAnchor)(jointType, body1, body2, worldAnchor, worldAxis);
+	public static GenericJointDescriptor DefaultGenericJointDescriptor() => ((delegate* unmanaged[Cdecl]<GenericJointDescriptor>) data.Data.defaultGenericJointDescriptor)();
+	public static ImpulseJointHandle AddGenericJoint(GenericJointDescriptor* descriptor) => ((delegate* unmanaged[Cdecl]<GenericJointDescriptor*, ImpulseJointHandle>) data.Data.addGenericJoint)(descriptor);
+	public static IntegrationParametersDescriptor DefaultIntegrationParametersDescriptor() => ((delegate* unmanaged[Cdecl]<IntegrationParametersDescriptor>) data.Data.defaultIntegrationParametersDescriptor)();
+	public static void SetIntegrationParametersFromDescriptor(IntegrationParametersDescriptor* descriptor) => ((delegate* unmanaged[Cdecl]<IntegrationParametersDescriptor*, void>) data.Data.setIntegrationParametersFromDescriptor)(descriptor);
+#else
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="default_collider_descriptor")]
+	public static extern unsafe ColliderDescriptor DefaultColliderDescriptor();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_collider_from_descriptor")]
+	public static extern unsafe ColliderHandle AddColliderFromDescriptor(ColliderDescriptor* descriptor);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="default_rigid_body_descriptor")]
+	public static extern unsafe RigidBodyDescriptor DefaultRigidBodyDescriptor();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_rigid_body_from_descriptor")]
+	public static extern unsafe RigidBodyHandle AddRigidBodyFromDescriptor(RigidBodyDescriptor* descriptor);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="default_joint_descriptor")]
+	public static extern unsafe JointDescriptor DefaultJointDescriptor();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_joint_from_descriptor")]
+	public static extern unsafe ImpulseJointHandle AddJointFromDescriptor(JointDescriptor* descriptor);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="joint_descriptor_from_world_anchor")]
+	public static extern unsafe JointDescriptor JointDescriptorFromWorldAnchor(uint jointType, RigidBodyHandle body1, RigidBodyHandle body2, float3 worldAnchor, float3 worldAxis);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="default_generic_joint_descriptor")]
+	public static extern unsafe GenericJointDescriptor DefaultGenericJointDescriptor();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="add_generic_joint")]
+	public static extern unsafe ImpulseJointHandle AddGenericJoint(GenericJointDescriptor* descriptor);
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="default_integration_parameters_descriptor")]
+	public static extern unsafe IntegrationParametersDescriptor DefaultIntegrationParametersDescriptor();
+	[DllImport(DllName, CallingConvention = Convention, EntryPoint="set_integration_parameters_from_descriptor")]
+	public static extern unsafe void SetIntegrationParametersFromDescriptor(IntegrationParametersDescriptor* descriptor);
+#endif
 
 #if UNITY_EDITOR && !DISABLE_DYNAMIC_RAPIER_LOAD
     // C# -> Rust
@@ -149,7 +368,44 @@ internal static unsafe class RapierBindings
             }
 
             // Load function pointers
-            init = NativeLoader.GetFunction(loaded_lib, "init");
+            executeCommands = NativeLoader.GetFunction(loaded_lib, "execute_commands");
+			getTransformPair = NativeLoader.GetFunction(loaded_lib, "get_transform_pair");
+			getInterpolatedTransform = NativeLoader.GetFunction(loaded_lib, "get_interpolated_transform");
+			advanceTimeAccumulator = NativeLoader.GetFunction(loaded_lib, "advance_time_accumulator");
+			resetTimeAccumulator = NativeLoader.GetFunction(loaded_lib, "reset_time_accumulator");
+			setJointMotor = NativeLoader.GetFunction(loaded_lib, "set_joint_motor");
+			disableJointMotor = NativeLoader.GetFunction(loaded_lib, "disable_joint_motor");
+			setJointLimits = NativeLoader.GetFunction(loaded_lib, "set_joint_limits");
+			disableJointLimits = NativeLoader.GetFunction(loaded_lib, "disable_joint_limits");
+			setTwistSwingLimits = NativeLoader.GetFunction(loaded_lib, "set_twist_swing_limits");
+			setJointAnchors = NativeLoader.GetFunction(loaded_lib, "set_joint_anchors");
+			setJointAxes = NativeLoader.GetFunction(loaded_lib, "set_joint_axes");
+			setJointFrames = NativeLoader.GetFunction(loaded_lib, "set_joint_frames");
+			setJointContactsEnabled = NativeLoader.GetFunction(loaded_lib, "set_joint_contacts_enabled");
+			setJointEnabled = NativeLoader.GetFunction(loaded_lib, "set_joint_enabled");
+			setJointBodies = NativeLoader.GetFunction(loaded_lib, "set_joint_bodies");
+			setJointBreakThreshold = NativeLoader.GetFunction(loaded_lib, "set_joint_break_threshold");
+			getBrokenJoints = NativeLoader.GetFunction(loaded_lib, "get_broken_joints");
+			getJointState = NativeLoader.GetFunction(loaded_lib, "get_joint_state");
+			getTransforms = NativeLoader.GetFunction(loaded_lib, "get_transforms");
+			getActiveBodyCount = NativeLoader.GetFunction(loaded_lib, "get_active_body_count");
+			getActiveTransforms = NativeLoader.GetFunction(loaded_lib, "get_active_transforms");
+			getMovedBodies = NativeLoader.GetFunction(loaded_lib, "get_moved_bodies");
+			getCollisionContacts = NativeLoader.GetFunction(loaded_lib, "get_collision_contacts");
+			setColliderContactForceEventThreshold = NativeLoader.GetFunction(loaded_lib, "set_collider_contact_force_event_threshold");
+			getContactForceEvents = NativeLoader.GetFunction(loaded_lib, "get_contact_force_events");
+			getCollisionStayEvents = NativeLoader.GetFunction(loaded_lib, "get_collision_stay_events");
+			setStayEventContacts = NativeLoader.GetFunction(loaded_lib, "set_stay_event_contacts");
+			setColliderStayEvents = NativeLoader.GetFunction(loaded_lib, "set_collider_stay_events");
+			setColliderUserId = NativeLoader.GetFunction(loaded_lib, "set_collider_user_id");
+			setColliderActiveEvents = NativeLoader.GetFunction(loaded_lib, "set_collider_active_events");
+			setColliderActiveCollisionTypes = NativeLoader.GetFunction(loaded_lib, "set_collider_active_collision_types");
+			setPhysicsHookCallbacks = NativeLoader.GetFunction(loaded_lib, "set_physics_hook_callbacks");
+			setColliderActiveHooks = NativeLoader.GetFunction(loaded_lib, "set_collider_active_hooks");
+			setColliderOneWayPlatform = NativeLoader.GetFunction(loaded_lib, "set_collider_one_way_platform");
+			setColliderConveyor = NativeLoader.GetFunction(loaded_lib, "set_collider_conveyor");
+			ignoreCollisionPair = NativeLoader.GetFunction(loaded_lib, "ignore_collision_pair");
+			init = NativeLoader.GetFunction(loaded_lib, "init");
 			helloWorld = NativeLoader.GetFunction(loaded_lib, "hello_world");
 			teardown = NativeLoader.GetFunction(loaded_lib, "teardown");
 			solve = NativeLoader.GetFunction(loaded_lib, "solve");
@@ -168,6 +424,8 @@ internal static unsafe class RapierBindings
 			addSphericalJoint = NativeLoader.GetFunction(loaded_lib, "add_spherical_joint");
 			addRevoluteJoint = NativeLoader.GetFunction(loaded_lib, "add_revolute_joint");
 			addPrismaticJoint = NativeLoader.GetFunction(loaded_lib, "add_prismatic_joint");
+			addSpringJoint = NativeLoader.GetFunction(loaded_lib, "add_spring_joint");
+			addRopeJoint = NativeLoader.GetFunction(loaded_lib, "add_rope_joint");
 			removeJoint = NativeLoader.GetFunction(loaded_lib, "remove_joint");
 			getTransform = NativeLoader.GetFunction(loaded_lib, "get_transform");
 			setTransformPosition = NativeLoader.GetFunction(loaded_lib, "set_transform_position");
@@ -181,10 +439,72 @@ internal static unsafe class RapierBindings
 			addTorque = NativeLoader.GetFunction(loaded_lib, "add_torque");
 			setIntegrationParameters = NativeLoader.GetFunction(loaded_lib, "set_integration_parameters");
 			castRay = NativeLoader.GetFunction(loaded_lib, "cast_ray");
+			addMultibodyJointFromDescriptor = NativeLoader.GetFunction(loaded_lib, "add_multibody_joint_from_descriptor");
+			addMultibodyGenericJoint = NativeLoader.GetFunction(loaded_lib, "add_multibody_generic_joint");
+			removeMultibodyJoint = NativeLoader.GetFunction(loaded_lib, "remove_multibody_joint");
+			setMultibodyJointMotor = NativeLoader.GetFunction(loaded_lib, "set_multibody_joint_motor");
+			disableMultibodyJointMotor = NativeLoader.GetFunction(loaded_lib, "disable_multibody_joint_motor");
+			setMultibodyJointLimits = NativeLoader.GetFunction(loaded_lib, "set_multibody_joint_limits");
+			getMultibodyJointDofCount = NativeLoader.GetFunction(loaded_lib, "get_multibody_joint_dof_count");
+			getMultibodyJointCoordinates = NativeLoader.GetFunction(loaded_lib, "get_multibody_joint_coordinates");
+			defaultInverseKinematicsDescriptor = NativeLoader.GetFunction(loaded_lib, "default_inverse_kinematics_descriptor");
+			solveMultibodyIk = NativeLoader.GetFunction(loaded_lib, "solve_multibody_ik");
+			createTransformStream = NativeLoader.GetFunction(loaded_lib, "create_transform_stream");
+			destroyTransformStream = NativeLoader.GetFunction(loaded_lib, "destroy_transform_stream");
+			registerTransformStreamBody = NativeLoader.GetFunction(loaded_lib, "register_transform_stream_body");
+			unregisterTransformStreamBody = NativeLoader.GetFunction(loaded_lib, "unregister_transform_stream_body");
+			defaultColliderDescriptor = NativeLoader.GetFunction(loaded_lib, "default_collider_descriptor");
+			addColliderFromDescriptor = NativeLoader.GetFunction(loaded_lib, "add_collider_from_descriptor");
+			defaultRigidBodyDescriptor = NativeLoader.GetFunction(loaded_lib, "default_rigid_body_descriptor");
+			addRigidBodyFromDescriptor = NativeLoader.GetFunction(loaded_lib, "add_rigid_body_from_descriptor");
+			defaultJointDescriptor = NativeLoader.GetFunction(loaded_lib, "default_joint_descriptor");
+			addJointFromDescriptor = NativeLoader.GetFunction(loaded_lib, "add_joint_from_descriptor");
+			jointDescriptorFromWorldAnchor = NativeLoader.GetFunction(loaded_lib, "joint_descriptor_from_world_anchor");
+			defaultGenericJointDescriptor = NativeLoader.GetFunction(loaded_lib, "default_generic_joint_descriptor");
+			addGenericJoint = NativeLoader.GetFunction(loaded_lib, "add_generic_joint");
+			defaultIntegrationParametersDescriptor = NativeLoader.GetFunction(loaded_lib, "default_integration_parameters_descriptor");
+			setIntegrationParametersFromDescriptor = NativeLoader.GetFunction(loaded_lib, "set_integration_parameters_from_descriptor");
         }
 
         // Raw function pointers
-        public IntPtr init;
+        public IntPtr executeCommands;
+		public IntPtr getTransformPair;
+		public IntPtr getInterpolatedTransform;
+		public IntPtr advanceTimeAccumulator;
+		public IntPtr resetTimeAccumulator;
+		public IntPtr setJointMotor;
+		public IntPtr disableJointMotor;
+		public IntPtr setJointLimits;
+		public IntPtr disableJointLimits;
+		public IntPtr setTwistSwingLimits;
+		public IntPtr setJointAnchors;
+		public IntPtr setJointAxes;
+		public IntPtr setJointFrames;
+		public IntPtr setJointContactsEnabled;
+		public IntPtr setJointEnabled;
+		public IntPtr setJointBodies;
+		public IntPtr setJointBreakThreshold;
+		public IntPtr getBrokenJoints;
+		public IntPtr getJointState;
+		public IntPtr getTransforms;
+		public IntPtr getActiveBodyCount;
+		public IntPtr getActiveTransforms;
+		public IntPtr getMovedBodies;
+		public IntPtr getCollisionContacts;
+		public IntPtr setColliderContactForceEventThreshold;
+		public IntPtr getContactForceEvents;
+		public IntPtr getCollisionStayEvents;
+		public IntPtr setStayEventContacts;
+		public IntPtr setColliderStayEvents;
+		public IntPtr setColliderUserId;
+		public IntPtr setColliderActiveEvents;
+		public IntPtr setColliderActiveCollisionTypes;
+		public IntPtr setPhysicsHookCallbacks;
+		public IntPtr setColliderActiveHooks;
+		public IntPtr setColliderOneWayPlatform;
+		public IntPtr setColliderConveyor;
+		public IntPtr ignoreCollisionPair;
+		public IntPtr init;
 		public IntPtr helloWorld;
 		public IntPtr teardown;
 		public IntPtr solve;
@@ -203,6 +523,8 @@ internal static unsafe class RapierBindings
 		public IntPtr addSphericalJoint;
 		public IntPtr addRevoluteJoint;
 		public IntPtr addPrismaticJoint;
+		public IntPtr addSpringJoint;
+		public IntPtr addRopeJoint;
 		public IntPtr removeJoint;
 		public IntPtr getTransform;
 		public IntPtr setTransformPosition;
@@ -216,6 +538,31 @@ internal static unsafe class RapierBindings
 		public IntPtr addTorque;
 		public IntPtr setIntegrationParameters;
 		public IntPtr castRay;
+		public IntPtr addMultibodyJointFromDescriptor;
+		public IntPtr addMultibodyGenericJoint;
+		public IntPtr removeMultibodyJoint;
+		public IntPtr setMultibodyJointMotor;
+		public IntPtr disableMultibodyJointMotor;
+		public IntPtr setMultibodyJointLimits;
+		public IntPtr getMultibodyJointDofCount;
+		public IntPtr getMultibodyJointCoordinates;
+		public IntPtr defaultInverseKinematicsDescriptor;
+		public IntPtr solveMultibodyIk;
+		public IntPtr createTransformStream;
+		public IntPtr destroyTransformStream;
+		public IntPtr registerTransformStreamBody;
+		public IntPtr unregisterTransformStreamBody;
+		public IntPtr defaultColliderDescriptor;
+		public IntPtr addColliderFromDescriptor;
+		public IntPtr defaultRigidBodyDescriptor;
+		public IntPtr addRigidBodyFromDescriptor;
+		public IntPtr defaultJointDescriptor;
+		public IntPtr addJointFromDescriptor;
+		public IntPtr jointDescriptorFromWorldAnchor;
+		public IntPtr defaultGenericJointDescriptor;
+		public IntPtr addGenericJoint;
+		public IntPtr defaultIntegrationParametersDescriptor;
+		public IntPtr setIntegrationParametersFromDescriptor;
 
         // Rust -> C# data
         public FunctionsToCallFromRust functionsToCallFromRust;
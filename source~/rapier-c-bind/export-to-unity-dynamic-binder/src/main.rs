@@ -13,11 +13,24 @@ static RUST_TYPE_TO_CSHARP: Map<&'static str, &'static str> = phf_map! {
     "SerializableRigidBodyType" => "RigidBodyType",
     "SerializableRigidBodyHandle" => "RigidBodyHandle",
     "SerializableImpulseJointHandle" => "ImpulseJointHandle",
+    "SerializableMultibodyJointHandle" => "MultibodyJointHandle",
+    "SerializableJointAxis" => "JointAxis",
+    "SerializableMotorModel" => "MotorModel",
     "SerializableCollisionEvent" => "CollisionEvent",
+    "SerializableContactPoint" => "ContactPoint",
+    "SerializableContactForceEvent" => "ContactForceEvent",
+    "SerializableJointBrokenEvent" => "JointBrokenEvent",
+    "SerializableJointState" => "JointState",
     "RaycastHit" => "RapierRaycastHit",
+    "Option<ContactPairFilterCallback>" => "IntPtr",
+    "Option<IntersectionPairFilterCallback>" => "IntPtr",
+    "Option<SolverContactModifierCallback>" => "IntPtr",
     "Vector3<float>" => "float3",
     "Vector2<float>" => "float2",
+    // Only ever used for rotations, stored as x, y, z, w like Unity.Mathematics' quaternion
+    "Vector4<float>" => "quaternion",
     "u32" => "uint",
+    "u64" => "ulong",
     "f32" => "float",
     "i32" => "int",
     "usize" => "UIntPtr",
@@ -41,13 +54,12 @@ fn main() -> Result<()> {
     let file = OpenOptions::new()
         .append(false)
         .write(true)
-        .open(path)
-        .or_else(|err| Err(err));
+        .open(path);
 
     let mut writer = BufWriter::new(file?);
     // write to file
     writeln!(&mut writer, "//#define DISABLE_DYNAMIC_RAPIER_LOAD")?;
-    writeln!(&mut writer, "")?;
+    writeln!(&mut writer)?;
     writeln!(&mut writer, "using System;")?;
     writeln!(&mut writer, "using System.IO;")?;
     writeln!(&mut writer, "using System.Runtime.InteropServices;")?;
@@ -56,7 +68,7 @@ fn main() -> Result<()> {
     writeln!(&mut writer, "using Unity.Burst;")?;
     writeln!(&mut writer, "using UnityEngine;")?;
     writeln!(&mut writer, "using Unity.Mathematics;")?;
-    writeln!(&mut writer, "")?;
+    writeln!(&mut writer)?;
 
     writeln!(
         &mut writer,
@@ -65,7 +77,7 @@ fn main() -> Result<()> {
     writeln!(&mut writer, "[UnityEditor.InitializeOnLoad]")?;
     writeln!(&mut writer, "#endif")?;
 
-    writeln!(&mut writer, "")?;
+    writeln!(&mut writer)?;
     writeln!(&mut writer, "[BurstCompile]")?;
     writeln!(&mut writer, "internal static unsafe class RapierBindings")?;
     writeln!(&mut writer, "{{")?;
@@ -275,7 +287,7 @@ fn is_extern_c(sig: &syn::Signature) -> bool {
         .unwrap_or(false)
 }
 
-impl<'a> Visit<'_> for FunctionVisitor {
+impl Visit<'_> for FunctionVisitor {
     fn visit_item_fn(&mut self, node: &syn::ItemFn) {
         // Check if this is the target function
         //self.function_name = node.sig.ident.to_string();
@@ -310,7 +322,7 @@ impl<'a> Visit<'_> for FunctionVisitor {
                 let some_type = get_typename(&pat_type.ty);
 
                 //println!("{:?}", some_type);
-                types.push(format!("{}", some_type));
+                types.push(some_type.to_string());
                 let param_name = to_camel_case(name.as_str());
                 parameter_list.push(format!("{} {}", some_type, param_name));
                 names.push(param_name);
@@ -359,7 +371,7 @@ fn get_typename(ty: &syn::Type) -> String {
                 pending_pointers += 1;
                 is_parsing_pointer = false;
             } else {
-                if let Some(csharp_type) = RUST_TYPE_TO_CSHARP.get(&punct.to_string().as_str()) {
+                if let Some(csharp_type) = RUST_TYPE_TO_CSHARP.get(punct.to_string().as_str()) {
                     some_type.push_str(csharp_type);
                 } else {
                     some_type.push_str(&format!("{}", punct));
@@ -376,7 +388,7 @@ fn get_typename(ty: &syn::Type) -> String {
 
     if pending_pointers > 0 {
         for _ in 0..pending_pointers {
-            some_type.push_str("*");
+            some_type.push('*');
         }
     }
 
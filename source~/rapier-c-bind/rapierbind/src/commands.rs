@@ -0,0 +1,179 @@
+use crate::handles::SerializableRigidBodyHandle;
+use crate::{ForceMode, PhysicsSolverData, get_mutable_physics_solver};
+use rapier3d::na::{Isometry, Quaternion, UnitQuaternion};
+use rapier3d::prelude::*;
+
+// Operation codes of the command stream, keep in sync with the C# command writer.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy)]
+enum CommandOp {
+    // payload: [vx, vy, vz]
+    SetLinearVelocity = 0,
+    // payload: [vx, vy, vz]
+    SetAngularVelocity = 1,
+    // payload: [fx, fy, fz], arg: ForceMode
+    AddForce = 2,
+    // payload: [tx, ty, tz], arg: ForceMode
+    AddTorque = 3,
+    // payload: [px, py, pz]
+    SetTransformPosition = 4,
+    // payload: [rx, ry, rz, rw]
+    SetTransformRotation = 5,
+    // payload: [px, py, pz, rx, ry, rz, rw]
+    SetTransform = 6,
+    // payload: unused
+    RemoveRigidBody = 7,
+    // payload: unused, arg: 1 for a strong wake up
+    WakeUp = 8,
+    // payload: unused
+    Sleep = 9,
+}
+
+impl TryFrom<u32> for CommandOp {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CommandOp::SetLinearVelocity),
+            1 => Ok(CommandOp::SetAngularVelocity),
+            2 => Ok(CommandOp::AddForce),
+            3 => Ok(CommandOp::AddTorque),
+            4 => Ok(CommandOp::SetTransformPosition),
+            5 => Ok(CommandOp::SetTransformRotation),
+            6 => Ok(CommandOp::SetTransform),
+            7 => Ok(CommandOp::RemoveRigidBody),
+            8 => Ok(CommandOp::WakeUp),
+            9 => Ok(CommandOp::Sleep),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TryFrom<u32> for ForceMode {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ForceMode::Force),
+            1 => Ok(ForceMode::Impulse),
+            2 => Ok(ForceMode::VelocityChange),
+            5 => Ok(ForceMode::Acceleration),
+            _ => Err(()),
+        }
+    }
+}
+
+// One fixed-size record of the command stream.
+// `op` and `arg` are plain integers so unknown values coming from C# can be rejected safely.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct RapierCommand {
+    op: u32,
+    arg: u32,
+    handle: SerializableRigidBodyHandle,
+    payload: [f32; 7],
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommandResult {
+    Ok = 0,
+    UnknownOp = 1,
+    InvalidHandle = 2,
+    InvalidArgument = 3,
+}
+
+impl PhysicsSolverData {
+    fn execute_command(&mut self, command: &RapierCommand) -> CommandResult {
+        let Ok(op) = CommandOp::try_from(command.op) else {
+            return CommandResult::UnknownOp;
+        };
+        let handle: RigidBodyHandle = command.handle.into();
+        let p = &command.payload;
+
+        if let CommandOp::RemoveRigidBody = op {
+            return if self.remove_rigid_body(handle) {
+                CommandResult::Ok
+            } else {
+                CommandResult::InvalidHandle
+            };
+        }
+
+        let dt = self.integration_parameters.dt;
+        let Some(rb) = self.rigid_body_set.get_mut(handle) else {
+            return CommandResult::InvalidHandle;
+        };
+        match op {
+            CommandOp::SetLinearVelocity => rb.set_linvel(vector![p[0], p[1], p[2]], true),
+            CommandOp::SetAngularVelocity => rb.set_angvel(vector![p[0], p[1], p[2]], true),
+            CommandOp::AddForce | CommandOp::AddTorque => {
+                let Ok(mode) = ForceMode::try_from(command.arg) else {
+                    return CommandResult::InvalidArgument;
+                };
+                let change = mode.velocity_change(vector![p[0], p[1], p[2]], rb.mass(), dt);
+                if let CommandOp::AddForce = op {
+                    rb.set_linvel(rb.linvel() + change, true);
+                } else {
+                    rb.set_angvel(rb.angvel() + change, true);
+                }
+            }
+            CommandOp::SetTransformPosition => {
+                let iso = Isometry::from_parts(
+                    Translation::from(vector![p[0], p[1], p[2]]),
+                    rb.next_position().rotation,
+                );
+                rb.set_next_kinematic_position(iso);
+            }
+            CommandOp::SetTransformRotation => {
+                rb.set_next_kinematic_rotation(UnitQuaternion::new_normalize(Quaternion::new(
+                    p[3], p[0], p[1], p[2],
+                )));
+            }
+            CommandOp::SetTransform => {
+                let iso = Isometry::from_parts(
+                    Translation::from(vector![p[0], p[1], p[2]]),
+                    UnitQuaternion::new_normalize(Quaternion::new(p[6], p[3], p[4], p[5])),
+                );
+                rb.set_next_kinematic_position(iso);
+            }
+            CommandOp::WakeUp => rb.wake_up(command.arg != 0),
+            CommandOp::Sleep => rb.sleep(),
+            CommandOp::RemoveRigidBody => unreachable!(),
+        }
+        CommandResult::Ok
+    }
+}
+
+/// Applies `count` commands in order.
+/// When `out_results` isn't null it must hold `count` entries and receives the result of each command.
+/// Returns the number of commands that failed, all of them when `commands_ptr` is null.
+#[unsafe(no_mangle)]
+extern "C" fn execute_commands(
+    commands_ptr: *const RapierCommand,
+    count: usize,
+    out_results: *mut CommandResult,
+) -> usize {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return count;
+    };
+    if commands_ptr.is_null() {
+        if count > 0 {
+            log::error!("execute_commands: null commands with a count of {}", count);
+        }
+        return count;
+    }
+    let commands = unsafe { std::slice::from_raw_parts(commands_ptr, count) };
+    let mut failures = 0;
+    for (i, command) in commands.iter().enumerate() {
+        let result = psd.execute_command(command);
+        if result != CommandResult::Ok {
+            failures += 1;
+        }
+        if !out_results.is_null() {
+            unsafe {
+                *out_results.add(i) = result;
+            }
+        }
+    }
+    failures
+}
@@ -0,0 +1,1014 @@
+// Descriptor structs for the creation API.
+// Every descriptor starts with a `version` field the caller sets to the version of the struct it was built against.
+// Fields added by later versions take their default value, so new fields can be appended at the end
+// without breaking callers built against an older layout. Each version ends at the end of its last field,
+// never at the size of the struct, as the caller's trailing padding holds no meaningful value.
+// Enums and booleans are plain u32 so unknown values coming from C# can be rejected safely,
+// booleans are true when non-zero.
+
+use crate::events::{
+    collision_types_from_mask, collision_types_to_mask, contact_force_active_events,
+};
+use crate::handles::{
+    SerializableColliderHandle, SerializableImpulseJointHandle, SerializableMotorModel,
+    SerializableRigidBodyHandle, SerializableRigidBodyType,
+};
+use crate::utils::unity_constraints_to_locked_axes;
+use crate::{PhysicsSolverData, get_mutable_physics_solver};
+use rapier3d::na::{Isometry, Isometry3, Quaternion, UnitQuaternion, Vector3, Vector4};
+use rapier3d::prelude::*;
+use std::num::NonZeroUsize;
+
+/// A struct read with `read_descriptor`, every field must be valid for any bit pattern.
+pub(crate) trait Descriptor: Default {
+    /// Byte offset of the end of the last field of each version, starting with version 1.
+    const VERSION_ENDS: &'static [usize];
+}
+
+/// Byte offset of the end of `$field`, for `Descriptor::VERSION_ENDS`.
+macro_rules! field_end {
+    ($descriptor:ty, $field:ident: $field_type:ty) => {
+        ::std::mem::offset_of!($descriptor, $field) + ::std::mem::size_of::<$field_type>()
+    };
+}
+pub(crate) use field_end;
+
+/// Reads a descriptor written by a caller that may know fewer fields than we do.
+/// Returns `None` if the pointer is null or the version is 0.
+pub(crate) unsafe fn read_descriptor<T: Descriptor>(ptr: *const T) -> Option<T> {
+    if ptr.is_null() {
+        return None;
+    }
+    let mut descriptor = T::default();
+    unsafe {
+        // Every descriptor starts with its version as a u32
+        let version = *(ptr as *const u32) as usize;
+        if version == 0 {
+            return None;
+        }
+        // A caller newer than us still gets every field we know about
+        let end = T::VERSION_ENDS[version.min(T::VERSION_ENDS.len()) - 1];
+        std::ptr::copy_nonoverlapping(ptr as *const u8, &mut descriptor as *mut T as *mut u8, end);
+    }
+    Some(descriptor)
+}
+
+pub(crate) fn latest_version<T: Descriptor>() -> u32 {
+    T::VERSION_ENDS.len() as u32
+}
+
+pub(crate) fn isometry_from_parts(
+    position: Vector3<f32>,
+    rotation: Vector4<f32>,
+) -> Isometry3<Real> {
+    Isometry::from_parts(
+        Translation::from(position),
+        UnitQuaternion::new_normalize(Quaternion::new(
+            rotation.w, rotation.x, rotation.y, rotation.z,
+        )),
+    )
+}
+
+// Collider
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy)]
+pub enum ColliderShape {
+    Cuboid = 0,
+    Sphere = 1,
+    Capsule = 2,
+    Mesh = 3,
+    ConvexMesh = 4,
+}
+
+impl TryFrom<u32> for ColliderShape {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ColliderShape::Cuboid),
+            1 => Ok(ColliderShape::Sphere),
+            2 => Ok(ColliderShape::Capsule),
+            3 => Ok(ColliderShape::Mesh),
+            4 => Ok(ColliderShape::ConvexMesh),
+            _ => Err(()),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ColliderDescriptor {
+    pub version: u32,
+    // ColliderShape
+    pub shape: u32,
+    // Cuboid
+    pub half_extents: Vector3<f32>,
+    // Sphere and capsule
+    pub radius: f32,
+    // Capsule, along the Y axis
+    pub half_height: f32,
+    // Mesh and convex mesh, 3 floats per vertex
+    pub vertices_ptr: *const f32,
+    pub vertices_count: usize,
+    // Mesh only, 3 indices per triangle
+    pub indices_ptr: *const u32,
+    pub indices_count: usize,
+    // Pose relative to the parent rigid body
+    pub position: Vector3<f32>,
+    pub rotation: Vector4<f32>,
+    pub density: f32,
+    pub friction: f32,
+    pub restitution: f32,
+    pub is_sensor: u32,
+    // Version 2
+    // Contact force events are reported once the total force on this collider goes over this,
+    // infinity disables them
+    pub contact_force_event_threshold: f32,
+    // Version 3
+    // Reported with every collision event involving this collider
+    pub user_id: u64,
+    // Version 4
+    // `ActiveEvents` bits, contact force events are also enabled by a finite threshold
+    pub active_events: u32,
+    // Body type pairs this collider interacts with, see `collision_types_from_mask`
+    pub active_collision_types: u32,
+    // Version 5
+    // `ActiveHooks` bits forwarded to the C# hook callbacks
+    pub active_hooks: u32,
+}
+
+impl Descriptor for ColliderDescriptor {
+    const VERSION_ENDS: &'static [usize] = &[
+        field_end!(ColliderDescriptor, is_sensor: u32),
+        field_end!(ColliderDescriptor, contact_force_event_threshold: f32),
+        field_end!(ColliderDescriptor, user_id: u64),
+        field_end!(ColliderDescriptor, active_collision_types: u32),
+        field_end!(ColliderDescriptor, active_hooks: u32),
+    ];
+}
+
+impl Default for ColliderDescriptor {
+    fn default() -> Self {
+        ColliderDescriptor {
+            version: latest_version::<ColliderDescriptor>(),
+            shape: ColliderShape::Cuboid as u32,
+            half_extents: vector![0.5, 0.5, 0.5],
+            radius: 0.5,
+            half_height: 0.5,
+            vertices_ptr: std::ptr::null(),
+            vertices_count: 0,
+            indices_ptr: std::ptr::null(),
+            indices_count: 0,
+            position: Vector3::zeros(),
+            rotation: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            density: 1.0,
+            friction: ColliderBuilder::default_friction(),
+            restitution: 0.0,
+            is_sensor: 0,
+            contact_force_event_threshold: f32::INFINITY,
+            user_id: 0,
+            active_events: ActiveEvents::COLLISION_EVENTS.bits(),
+            active_collision_types: collision_types_to_mask(ActiveCollisionTypes::default()),
+            active_hooks: 0,
+        }
+    }
+}
+
+impl ColliderDescriptor {
+    fn vertices(&self) -> Vec<Point<Real>> {
+        if self.vertices_ptr.is_null() {
+            return Vec::new();
+        }
+        let vertices_flat =
+            unsafe { std::slice::from_raw_parts(self.vertices_ptr, self.vertices_count * 3) };
+        vertices_flat
+            .chunks_exact(3)
+            .map(|v| point![v[0], v[1], v[2]])
+            .collect()
+    }
+
+    fn indices(&self) -> Vec<[u32; 3]> {
+        if self.indices_ptr.is_null() {
+            return Vec::new();
+        }
+        let indices_flat =
+            unsafe { std::slice::from_raw_parts(self.indices_ptr, self.indices_count * 3) };
+        indices_flat
+            .chunks_exact(3)
+            .map(|i| [i[0], i[1], i[2]])
+            .collect()
+    }
+
+    fn builder(&self, shape: ColliderShape) -> Option<ColliderBuilder> {
+        match shape {
+            ColliderShape::Cuboid => Some(ColliderBuilder::cuboid(
+                self.half_extents.x,
+                self.half_extents.y,
+                self.half_extents.z,
+            )),
+            ColliderShape::Sphere => Some(ColliderBuilder::ball(self.radius)),
+            ColliderShape::Capsule => {
+                Some(ColliderBuilder::capsule_y(self.half_height, self.radius))
+            }
+            ColliderShape::Mesh => ColliderBuilder::trimesh(self.vertices(), self.indices()).ok(),
+            ColliderShape::ConvexMesh => ColliderBuilder::convex_hull(&self.vertices()),
+        }
+    }
+}
+
+impl PhysicsSolverData {
+    pub(crate) fn insert_collider(
+        &mut self,
+        descriptor: &ColliderDescriptor,
+    ) -> SerializableColliderHandle {
+        let Ok(shape) = ColliderShape::try_from(descriptor.shape) else {
+            log::warn!(
+                "Failed to create collider, unknown shape {}",
+                descriptor.shape
+            );
+            return ColliderHandle::invalid().into();
+        };
+        let Some(builder) = descriptor.builder(shape) else {
+            log::warn!("Failed to create {:?} collider", shape);
+            return ColliderHandle::invalid().into();
+        };
+        let collider = builder
+            .position(isometry_from_parts(
+                descriptor.position,
+                descriptor.rotation,
+            ))
+            .active_events(contact_force_active_events(
+                ActiveEvents::from_bits_truncate(descriptor.active_events),
+                descriptor.contact_force_event_threshold,
+            ))
+            .active_collision_types(collision_types_from_mask(descriptor.active_collision_types))
+            .contact_force_event_threshold(descriptor.contact_force_event_threshold)
+            .density(descriptor.density)
+            .friction(descriptor.friction)
+            .restitution(descriptor.restitution)
+            .sensor(descriptor.is_sensor != 0)
+            .user_data(descriptor.user_id as u128)
+            .build();
+        let handle = self.collider_set.insert(collider);
+        self.set_managed_hooks(
+            handle,
+            ActiveHooks::from_bits_truncate(descriptor.active_hooks),
+        );
+        handle.into()
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn default_collider_descriptor() -> ColliderDescriptor {
+    ColliderDescriptor::default()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn add_collider_from_descriptor(
+    descriptor: *const ColliderDescriptor,
+) -> SerializableColliderHandle {
+    let Some(descriptor) = (unsafe { read_descriptor(descriptor) }) else {
+        log::warn!("add_collider_from_descriptor called with a null or unversioned descriptor");
+        return ColliderHandle::invalid().into();
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ColliderHandle::invalid().into();
+    };
+    psd.insert_collider(&descriptor)
+}
+
+// RigidBody
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RigidBodyDescriptor {
+    pub version: u32,
+    // SerializableRigidBodyType
+    pub body_type: u32,
+    // Collider to attach to the body, an invalid handle attaches nothing
+    pub collider: SerializableColliderHandle,
+    pub position: Vector3<f32>,
+    pub rotation: Vector4<f32>,
+    pub linear_velocity: Vector3<f32>,
+    pub angular_velocity: Vector3<f32>,
+    // Unity RigidbodyConstraints bit mask
+    pub constraints: u32,
+    pub linear_drag: f32,
+    pub angular_drag: f32,
+    pub gravity_scale: f32,
+    pub enable_ccd: u32,
+    pub can_sleep: u32,
+}
+
+impl Descriptor for RigidBodyDescriptor {
+    const VERSION_ENDS: &'static [usize] = &[field_end!(RigidBodyDescriptor, can_sleep: u32)];
+}
+
+impl Default for RigidBodyDescriptor {
+    fn default() -> Self {
+        RigidBodyDescriptor {
+            version: latest_version::<RigidBodyDescriptor>(),
+            body_type: SerializableRigidBodyType::Dynamic as u32,
+            collider: ColliderHandle::invalid().into(),
+            position: Vector3::zeros(),
+            rotation: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            linear_velocity: Vector3::zeros(),
+            angular_velocity: Vector3::zeros(),
+            constraints: 0,
+            linear_drag: 0.0,
+            angular_drag: 0.0,
+            gravity_scale: 1.0,
+            enable_ccd: 0,
+            can_sleep: 1,
+        }
+    }
+}
+
+impl PhysicsSolverData {
+    pub(crate) fn insert_rigid_body(
+        &mut self,
+        descriptor: &RigidBodyDescriptor,
+    ) -> SerializableRigidBodyHandle {
+        let Ok(body_type) = SerializableRigidBodyType::try_from(descriptor.body_type) else {
+            log::warn!(
+                "Failed to create rigid body, unknown body type {}",
+                descriptor.body_type
+            );
+            return RigidBodyHandle::invalid().into();
+        };
+        let rigid_body = RigidBodyBuilder::new(body_type.into())
+            .position(isometry_from_parts(
+                descriptor.position,
+                descriptor.rotation,
+            ))
+            .linvel(descriptor.linear_velocity)
+            .angvel(descriptor.angular_velocity)
+            .locked_axes(unity_constraints_to_locked_axes(descriptor.constraints))
+            .linear_damping(descriptor.linear_drag)
+            .angular_damping(descriptor.angular_drag)
+            .gravity_scale(descriptor.gravity_scale)
+            .ccd_enabled(descriptor.enable_ccd != 0)
+            .can_sleep(descriptor.can_sleep != 0)
+            .build();
+
+        let rb_handle = self.rigid_body_set.insert(rigid_body);
+        let collider: ColliderHandle = descriptor.collider.into();
+        if self.collider_set.contains(collider) {
+            self.collider_set
+                .set_parent(collider, Some(rb_handle), &mut self.rigid_body_set);
+        }
+        rb_handle.into()
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn default_rigid_body_descriptor() -> RigidBodyDescriptor {
+    RigidBodyDescriptor::default()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn add_rigid_body_from_descriptor(
+    descriptor: *const RigidBodyDescriptor,
+) -> SerializableRigidBodyHandle {
+    let Some(descriptor) = (unsafe { read_descriptor(descriptor) }) else {
+        log::warn!("add_rigid_body_from_descriptor called with a null or unversioned descriptor");
+        return RigidBodyHandle::invalid().into();
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return RigidBodyHandle::invalid().into();
+    };
+    psd.insert_rigid_body(&descriptor)
+}
+
+// Joints
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JointType {
+    Fixed = 0,
+    Spherical = 1,
+    Revolute = 2,
+    Prismatic = 3,
+    Spring = 4,
+    Rope = 5,
+}
+
+impl TryFrom<u32> for JointType {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(JointType::Fixed),
+            1 => Ok(JointType::Spherical),
+            2 => Ok(JointType::Revolute),
+            3 => Ok(JointType::Prismatic),
+            4 => Ok(JointType::Spring),
+            5 => Ok(JointType::Rope),
+            _ => Err(()),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct JointDescriptor {
+    pub version: u32,
+    // JointType
+    pub joint_type: u32,
+    pub body1: SerializableRigidBodyHandle,
+    pub body2: SerializableRigidBodyHandle,
+    // Anchors in the local space of each body
+    pub local_anchor1: Vector3<f32>,
+    pub local_anchor2: Vector3<f32>,
+    // Revolute and prismatic only
+    pub axis: Vector3<f32>,
+    // Limits along the axis for prismatic joints, around it in radians for revolute joints,
+    // and on the distance between the anchors for spring and rope joints
+    pub limits_enabled: u32,
+    pub limit_min: f32,
+    pub limit_max: f32,
+    pub contacts_enabled: u32,
+    // Version 2
+    // Spring only
+    pub rest_length: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+    // Version 3
+    // When set, the local anchors are combined with these rotations into full local frames
+    // that replace the per-type defaults, the joint axis then being the frames' X axis
+    pub use_local_frames: u32,
+    pub local_rotation1: Vector4<f32>,
+    pub local_rotation2: Vector4<f32>,
+}
+
+impl Descriptor for JointDescriptor {
+    const VERSION_ENDS: &'static [usize] = &[
+        field_end!(JointDescriptor, contacts_enabled: u32),
+        field_end!(JointDescriptor, damping: f32),
+        field_end!(JointDescriptor, local_rotation2: Vector4<f32>),
+    ];
+}
+
+impl Default for JointDescriptor {
+    fn default() -> Self {
+        JointDescriptor {
+            version: latest_version::<JointDescriptor>(),
+            joint_type: JointType::Fixed as u32,
+            body1: RigidBodyHandle::invalid().into(),
+            body2: RigidBodyHandle::invalid().into(),
+            local_anchor1: Vector3::zeros(),
+            local_anchor2: Vector3::zeros(),
+            axis: Vector3::x(),
+            limits_enabled: 0,
+            limit_min: 0.0,
+            limit_max: 0.0,
+            contacts_enabled: 1,
+            rest_length: 0.0,
+            stiffness: 0.0,
+            damping: 0.0,
+            use_local_frames: 0,
+            local_rotation1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            local_rotation2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+        }
+    }
+}
+
+impl PhysicsSolverData {
+    /// Builds the joint data described by `descriptor`, shared by impulse and multibody joints.
+    /// Returns `None` if the joint type is unknown or either body handle is invalid.
+    pub(crate) fn build_joint(&self, descriptor: &JointDescriptor) -> Option<GenericJoint> {
+        let Ok(joint_type) = JointType::try_from(descriptor.joint_type) else {
+            log::warn!(
+                "Failed to create joint, unknown joint type {}",
+                descriptor.joint_type
+            );
+            return None;
+        };
+        let (Some(rb1), Some(rb2)) = (
+            self.rigid_body_set.get(descriptor.body1.into()),
+            self.rigid_body_set.get(descriptor.body2.into()),
+        ) else {
+            log::warn!(
+                "Failed to create {:?} joint, invalid rigid body handle",
+                joint_type
+            );
+            return None;
+        };
+        let point1: Point<Real> = descriptor.local_anchor1.into();
+        let point2: Point<Real> = descriptor.local_anchor2.into();
+        let axis: UnitVector<Real> = UnitVector::new_normalize(descriptor.axis);
+
+        let mut joint: GenericJoint = match joint_type {
+            // Fixed and spherical joints keep the bodies' current rotations as their frames
+            JointType::Fixed => FixedJointBuilder::new()
+                .local_frame1(Isometry::from_parts(point1.into(), rb1.position().rotation))
+                .local_frame2(Isometry::from_parts(point2.into(), rb2.position().rotation))
+                .into(),
+            JointType::Spherical => SphericalJointBuilder::new()
+                .local_frame1(Isometry::from_parts(point1.into(), rb1.position().rotation))
+                .local_frame2(Isometry::from_parts(point2.into(), rb2.position().rotation))
+                .into(),
+            JointType::Revolute => RevoluteJointBuilder::new(axis)
+                .local_anchor1(point1)
+                .local_anchor2(point2)
+                .into(),
+            JointType::Prismatic => PrismaticJointBuilder::new(axis)
+                .local_anchor1(point1)
+                .local_anchor2(point2)
+                .into(),
+            JointType::Spring => SpringJointBuilder::new(
+                descriptor.rest_length,
+                descriptor.stiffness,
+                descriptor.damping,
+            )
+            .local_anchor1(point1)
+            .local_anchor2(point2)
+            .into(),
+            // A rope is nothing but its max distance, so it is always limited
+            JointType::Rope => RopeJointBuilder::new(descriptor.limit_max)
+                .local_anchor1(point1)
+                .local_anchor2(point2)
+                .into(),
+        };
+        if descriptor.use_local_frames != 0 {
+            joint.set_local_frame1(isometry_from_parts(
+                descriptor.local_anchor1,
+                descriptor.local_rotation1,
+            ));
+            joint.set_local_frame2(isometry_from_parts(
+                descriptor.local_anchor2,
+                descriptor.local_rotation2,
+            ));
+        }
+        if descriptor.limits_enabled != 0 {
+            let limited_axis = match joint_type {
+                JointType::Revolute => Some(JointAxis::AngX),
+                JointType::Prismatic | JointType::Spring | JointType::Rope => Some(JointAxis::LinX),
+                _ => None,
+            };
+            if let Some(axis) = limited_axis {
+                joint.set_limits(axis, [descriptor.limit_min, descriptor.limit_max]);
+            }
+        }
+        joint.set_contacts_enabled(descriptor.contacts_enabled != 0);
+        Some(joint)
+    }
+
+    pub(crate) fn insert_joint(
+        &mut self,
+        descriptor: &JointDescriptor,
+    ) -> SerializableImpulseJointHandle {
+        let Some(joint) = self.build_joint(descriptor) else {
+            return ImpulseJointHandle::invalid().into();
+        };
+        self.impulse_joint_set
+            .insert(
+                descriptor.body1.into(),
+                descriptor.body2.into(),
+                joint,
+                false,
+            )
+            .into()
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn default_joint_descriptor() -> JointDescriptor {
+    JointDescriptor::default()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn add_joint_from_descriptor(
+    descriptor: *const JointDescriptor,
+) -> SerializableImpulseJointHandle {
+    let Some(descriptor) = (unsafe { read_descriptor(descriptor) }) else {
+        log::warn!("add_joint_from_descriptor called with a null or unversioned descriptor");
+        return ImpulseJointHandle::invalid().into();
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    psd.insert_joint(&descriptor)
+}
+
+/// Builds a joint descriptor whose local frames both sit on `world_anchor`, with their X axis
+/// along `world_axis`, from the current poses of the bodies.
+/// This is the equivalent of Unity's `autoConfigureConnectedAnchor`, the joint starts at rest.
+/// `joint_type` is a `JointType`, unknown values are reported when the joint is created.
+#[unsafe(no_mangle)]
+extern "C" fn joint_descriptor_from_world_anchor(
+    joint_type: u32,
+    body1: SerializableRigidBodyHandle,
+    body2: SerializableRigidBodyHandle,
+    world_anchor: Vector3<f32>,
+    world_axis: Vector3<f32>,
+) -> JointDescriptor {
+    let mut descriptor = JointDescriptor {
+        joint_type,
+        body1,
+        body2,
+        ..Default::default()
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return descriptor;
+    };
+    let (Some(rb1), Some(rb2)) = (
+        psd.rigid_body_set.get(body1.into()),
+        psd.rigid_body_set.get(body2.into()),
+    ) else {
+        log::warn!("joint_descriptor_from_world_anchor called with an invalid rigid body handle");
+        return descriptor;
+    };
+
+    // rotation_between has no answer for opposite vectors, any half turn around a normal will do
+    let rotation =
+        UnitQuaternion::rotation_between(&Vector3::x(), &world_axis).unwrap_or_else(|| {
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), std::f32::consts::PI)
+        });
+    let world_frame = Isometry::from_parts(Translation::from(world_anchor), rotation);
+    let frame1 = rb1.position().inv_mul(&world_frame);
+    let frame2 = rb2.position().inv_mul(&world_frame);
+
+    descriptor.use_local_frames = 1;
+    descriptor.local_anchor1 = frame1.translation.vector;
+    descriptor.local_anchor2 = frame2.translation.vector;
+    descriptor.local_rotation1 = frame1.rotation.coords;
+    descriptor.local_rotation2 = frame2.rotation.coords;
+    descriptor.axis = frame1.rotation * Vector3::x();
+    descriptor
+}
+
+// Generic joints
+
+// Order of the per-axis arrays below, matching the bits of the axis masks
+const JOINT_AXES: [JointAxis; 6] = [
+    JointAxis::LinX,
+    JointAxis::LinY,
+    JointAxis::LinZ,
+    JointAxis::AngX,
+    JointAxis::AngY,
+    JointAxis::AngZ,
+];
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct JointMotorDescriptor {
+    pub target_pos: f32,
+    pub target_vel: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+    pub max_force: f32,
+    // SerializableMotorModel
+    pub model: u32,
+}
+
+impl Default for JointMotorDescriptor {
+    fn default() -> Self {
+        JointMotorDescriptor {
+            target_pos: 0.0,
+            target_vel: 0.0,
+            stiffness: 0.0,
+            damping: 0.0,
+            max_force: Real::MAX,
+            model: SerializableMotorModel::AccelerationBased as u32,
+        }
+    }
+}
+
+// Six degree of freedom joint, the counterpart of Unity's ConfigurableJoint.
+// Axis masks use bit 0..5 for LinX, LinY, LinZ, AngX, AngY, AngZ of the joint frame.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct GenericJointDescriptor {
+    pub version: u32,
+    pub body1: SerializableRigidBodyHandle,
+    pub body2: SerializableRigidBodyHandle,
+    // Joint frames in the local space of each body
+    pub local_frame1_position: Vector3<f32>,
+    pub local_frame1_rotation: Vector4<f32>,
+    pub local_frame2_position: Vector3<f32>,
+    pub local_frame2_rotation: Vector4<f32>,
+    pub locked_axes: u32,
+    pub limit_axes: u32,
+    pub motor_axes: u32,
+    pub coupled_axes: u32,
+    // Indexed by axis, in radians for angular axes
+    pub limits_min: [f32; 6],
+    pub limits_max: [f32; 6],
+    pub motors: [JointMotorDescriptor; 6],
+    pub contacts_enabled: u32,
+}
+
+impl Descriptor for GenericJointDescriptor {
+    const VERSION_ENDS: &'static [usize] =
+        &[field_end!(GenericJointDescriptor, contacts_enabled: u32)];
+}
+
+impl Default for GenericJointDescriptor {
+    fn default() -> Self {
+        GenericJointDescriptor {
+            version: latest_version::<GenericJointDescriptor>(),
+            body1: RigidBodyHandle::invalid().into(),
+            body2: RigidBodyHandle::invalid().into(),
+            local_frame1_position: Vector3::zeros(),
+            local_frame1_rotation: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            local_frame2_position: Vector3::zeros(),
+            local_frame2_rotation: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            locked_axes: 0,
+            limit_axes: 0,
+            motor_axes: 0,
+            coupled_axes: 0,
+            limits_min: [0.0; 6],
+            limits_max: [0.0; 6],
+            motors: [JointMotorDescriptor::default(); 6],
+            contacts_enabled: 1,
+        }
+    }
+}
+
+impl GenericJointDescriptor {
+    /// Returns `None` if a motor of an axis in `motor_axes` has an unknown model.
+    pub(crate) fn build(&self) -> Option<GenericJoint> {
+        let mut joint =
+            GenericJointBuilder::new(JointAxesMask::from_bits_truncate(self.locked_axes as u8))
+                .local_frame1(isometry_from_parts(
+                    self.local_frame1_position,
+                    self.local_frame1_rotation,
+                ))
+                .local_frame2(isometry_from_parts(
+                    self.local_frame2_position,
+                    self.local_frame2_rotation,
+                ))
+                .coupled_axes(JointAxesMask::from_bits_truncate(self.coupled_axes as u8))
+                .contacts_enabled(self.contacts_enabled != 0)
+                .build();
+
+        for (i, axis) in JOINT_AXES.into_iter().enumerate() {
+            if self.limit_axes & (1 << i) != 0 {
+                joint.set_limits(axis, [self.limits_min[i], self.limits_max[i]]);
+            }
+            if self.motor_axes & (1 << i) != 0 {
+                let motor = &self.motors[i];
+                let Ok(model) = SerializableMotorModel::try_from(motor.model) else {
+                    log::warn!(
+                        "Failed to create generic joint, unknown motor model {} on {:?}",
+                        motor.model,
+                        axis
+                    );
+                    return None;
+                };
+                joint
+                    .set_motor(
+                        axis,
+                        motor.target_pos,
+                        motor.target_vel,
+                        motor.stiffness,
+                        motor.damping,
+                    )
+                    .set_motor_max_force(axis, motor.max_force)
+                    .set_motor_model(axis, model.into());
+            }
+        }
+        Some(joint)
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn default_generic_joint_descriptor() -> GenericJointDescriptor {
+    GenericJointDescriptor::default()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn add_generic_joint(
+    descriptor: *const GenericJointDescriptor,
+) -> SerializableImpulseJointHandle {
+    let Some(descriptor) = (unsafe { read_descriptor(descriptor) }) else {
+        log::warn!("add_generic_joint called with a null or unversioned descriptor");
+        return ImpulseJointHandle::invalid().into();
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    let body1: RigidBodyHandle = descriptor.body1.into();
+    let body2: RigidBodyHandle = descriptor.body2.into();
+    if !psd.rigid_body_set.contains(body1) || !psd.rigid_body_set.contains(body2) {
+        log::warn!("Failed to create generic joint, invalid rigid body handle");
+        return ImpulseJointHandle::invalid().into();
+    }
+    let Some(joint) = descriptor.build() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    psd.impulse_joint_set
+        .insert(body1, body2, joint, false)
+        .into()
+}
+
+// Integration parameters
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct IntegrationParametersDescriptor {
+    pub version: u32,
+    // Time step
+    pub dt: f32,
+    // Solver parameters
+    pub solver_iterations: usize,
+    pub solver_pgs_iterations: usize,
+    pub solver_additional_friction_iterations: usize,
+    pub solver_stabilization_iterations: usize,
+    pub ccd_substeps: usize,
+    // Damping parameters
+    pub contact_damping_ratio: f32,
+    pub joint_damping_ratio: f32,
+    // Frequency parameters
+    pub contact_frequency: f32,
+    pub joint_frequency: f32,
+    // Prediction parameters
+    pub prediction_distance: f32,
+    pub max_corrective_velocity: f32,
+    // Length unit
+    pub length_unit: f32,
+}
+
+impl Descriptor for IntegrationParametersDescriptor {
+    const VERSION_ENDS: &'static [usize] =
+        &[field_end!(IntegrationParametersDescriptor, length_unit: f32)];
+}
+
+impl Default for IntegrationParametersDescriptor {
+    fn default() -> Self {
+        let defaults = IntegrationParameters::default();
+        IntegrationParametersDescriptor {
+            version: latest_version::<IntegrationParametersDescriptor>(),
+            dt: 1.0 / 50.0,
+            solver_iterations: defaults.num_solver_iterations.get(),
+            solver_pgs_iterations: defaults.num_internal_pgs_iterations,
+            solver_additional_friction_iterations: defaults.num_additional_friction_iterations,
+            solver_stabilization_iterations: defaults.num_internal_stabilization_iterations,
+            ccd_substeps: defaults.max_ccd_substeps,
+            contact_damping_ratio: defaults.contact_damping_ratio,
+            joint_damping_ratio: defaults.joint_damping_ratio,
+            contact_frequency: defaults.contact_natural_frequency,
+            joint_frequency: defaults.joint_natural_frequency,
+            prediction_distance: defaults.normalized_prediction_distance,
+            max_corrective_velocity: defaults.normalized_max_corrective_velocity,
+            length_unit: defaults.length_unit,
+        }
+    }
+}
+
+impl PhysicsSolverData {
+    pub(crate) fn apply_integration_parameters(
+        &mut self,
+        descriptor: &IntegrationParametersDescriptor,
+    ) {
+        let params = &mut self.integration_parameters;
+        params.dt = descriptor.dt;
+        params.min_ccd_dt = descriptor.dt / 100.0;
+        params.num_solver_iterations = NonZeroUsize::new(descriptor.solver_iterations)
+            .unwrap_or(NonZeroUsize::new(4).unwrap());
+        params.num_internal_pgs_iterations = descriptor.solver_pgs_iterations;
+        params.num_additional_friction_iterations =
+            descriptor.solver_additional_friction_iterations;
+        params.num_internal_stabilization_iterations = descriptor.solver_stabilization_iterations;
+        params.max_ccd_substeps = descriptor.ccd_substeps;
+        params.contact_damping_ratio = descriptor.contact_damping_ratio;
+        params.joint_damping_ratio = descriptor.joint_damping_ratio;
+        params.contact_natural_frequency = descriptor.contact_frequency;
+        params.joint_natural_frequency = descriptor.joint_frequency;
+        params.normalized_prediction_distance = descriptor.prediction_distance;
+        params.normalized_max_corrective_velocity = descriptor.max_corrective_velocity;
+        params.length_unit = descriptor.length_unit;
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn default_integration_parameters_descriptor() -> IntegrationParametersDescriptor {
+    IntegrationParametersDescriptor::default()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn set_integration_parameters_from_descriptor(
+    descriptor: *const IntegrationParametersDescriptor,
+) {
+    let Some(descriptor) = (unsafe { read_descriptor(descriptor) }) else {
+        log::warn!(
+            "set_integration_parameters_from_descriptor called with a null or unversioned descriptor"
+        );
+        return;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.apply_integration_parameters(&descriptor);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A caller built against version 1 leaves garbage, or padding, where later fields now sit
+    fn version1_collider() -> ColliderDescriptor {
+        let mut descriptor = ColliderDescriptor {
+            version: 1,
+            shape: ColliderShape::Sphere as u32,
+            radius: 2.0,
+            is_sensor: 1,
+            ..Default::default()
+        };
+        descriptor.contact_force_event_threshold = 0.0;
+        descriptor.user_id = 42;
+        descriptor.active_events = u32::MAX;
+        descriptor.active_collision_types = 0;
+        descriptor.active_hooks = u32::MAX;
+        descriptor
+    }
+
+    #[test]
+    fn truncated_descriptor_defaults_later_fields() {
+        let descriptor = unsafe { read_descriptor(&version1_collider()) }.unwrap();
+        let defaults = ColliderDescriptor::default();
+        assert_eq!(descriptor.shape, ColliderShape::Sphere as u32);
+        assert_eq!(descriptor.radius, 2.0);
+        assert_eq!(descriptor.is_sensor, 1);
+        assert_eq!(
+            descriptor.contact_force_event_threshold,
+            defaults.contact_force_event_threshold
+        );
+        assert_eq!(descriptor.user_id, defaults.user_id);
+        assert_eq!(descriptor.active_events, defaults.active_events);
+        assert_eq!(
+            descriptor.active_collision_types,
+            defaults.active_collision_types
+        );
+        assert_eq!(descriptor.active_hooks, defaults.active_hooks);
+    }
+
+    #[test]
+    fn version1_collider_keeps_contact_force_events_off() {
+        let descriptor = unsafe { read_descriptor(&version1_collider()) }.unwrap();
+        let mut psd = PhysicsSolverData::default();
+        let handle = psd.insert_collider(&descriptor);
+        let collider = &psd.collider_set[ColliderHandle::from(handle)];
+        assert!(
+            !collider
+                .active_events()
+                .contains(ActiveEvents::CONTACT_FORCE_EVENTS)
+        );
+        assert_eq!(collider.contact_force_event_threshold(), f32::INFINITY);
+    }
+
+    #[test]
+    fn newer_version_reads_every_known_field() {
+        let mut written = version1_collider();
+        written.version = u32::MAX;
+        let descriptor = unsafe { read_descriptor(&written) }.unwrap();
+        assert_eq!(descriptor.contact_force_event_threshold, 0.0);
+        assert_eq!(descriptor.user_id, 42);
+        assert_eq!(descriptor.active_hooks, u32::MAX);
+    }
+
+    #[test]
+    fn version_zero_and_null_are_rejected() {
+        let written = JointDescriptor {
+            version: 0,
+            ..Default::default()
+        };
+        assert!(unsafe { read_descriptor(&written) }.is_none());
+        assert!(unsafe { read_descriptor::<JointDescriptor>(std::ptr::null()) }.is_none());
+    }
+
+    #[test]
+    fn version_ends_are_increasing_and_fit_the_struct() {
+        fn check<T: Descriptor>() {
+            assert!(T::VERSION_ENDS.windows(2).all(|ends| ends[0] < ends[1]));
+            assert!(*T::VERSION_ENDS.last().unwrap() <= size_of::<T>());
+        }
+        check::<ColliderDescriptor>();
+        check::<RigidBodyDescriptor>();
+        check::<JointDescriptor>();
+        check::<GenericJointDescriptor>();
+        check::<IntegrationParametersDescriptor>();
+    }
+
+    #[test]
+    fn unknown_enum_values_are_rejected() {
+        let mut psd = PhysicsSolverData::default();
+        let collider = psd.insert_collider(&ColliderDescriptor {
+            shape: 99,
+            ..Default::default()
+        });
+        assert!(!psd.collider_set.contains(collider.into()));
+        let body = psd.insert_rigid_body(&RigidBodyDescriptor {
+            body_type: 99,
+            ..Default::default()
+        });
+        assert!(!psd.rigid_body_set.contains(body.into()));
+        let joint = JointDescriptor {
+            joint_type: 99,
+            ..Default::default()
+        };
+        assert!(psd.build_joint(&joint).is_none());
+    }
+}
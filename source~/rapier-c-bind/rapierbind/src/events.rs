@@ -0,0 +1,503 @@
+use crate::handles::{SerializableColliderHandle, SerializableRigidBodyHandle};
+use crate::{PhysicsSolverData, RawArray, get_mutable_physics_solver};
+use rapier3d::na::Vector3;
+use rapier3d::prelude::*;
+use std::cmp::Ordering;
+use std::mem;
+use std::sync::Mutex;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SerializableCollisionEvent {
+    collider1: SerializableColliderHandle,
+    collider2: SerializableColliderHandle,
+    is_started: bool,
+    // Range of this event's points in `get_collision_contacts`, empty for stopped events and sensors
+    contacts_start: u32,
+    contacts_count: u32,
+    // Parent bodies and user ids of both colliders, still set when a collider was removed with its body
+    body1: SerializableRigidBodyHandle,
+    body2: SerializableRigidBodyHandle,
+    user_id1: u64,
+    user_id2: u64,
+    // At least one of the colliders is a sensor, so this is a trigger event
+    is_sensor: bool,
+    // The pair stopped touching because one of the colliders was removed
+    removed: bool,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SerializableContactPoint {
+    // World-space point on the event's first collider
+    point: Vector3<f32>,
+    // World-space normal pointing from the first collider towards the second
+    normal: Vector3<f32>,
+    // Positive when the colliders overlap, negative when the point is only predicted
+    depth: f32,
+    // Normal impulse the point applied during the last step
+    impulse: f32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SerializableContactForceEvent {
+    collider1: SerializableColliderHandle,
+    collider2: SerializableColliderHandle,
+    // Sum of the forces at every contact point, and the sum of their magnitudes
+    total_force: Vector3<f32>,
+    total_force_magnitude: f32,
+    // Strongest force at a single contact point, the direction is a world-space unit vector
+    max_force_direction: Vector3<f32>,
+    max_force_magnitude: f32,
+}
+
+impl From<ContactForceEvent> for SerializableContactForceEvent {
+    fn from(value: ContactForceEvent) -> Self {
+        SerializableContactForceEvent {
+            collider1: value.collider1.into(),
+            collider2: value.collider2.into(),
+            total_force: value.total_force,
+            total_force_magnitude: value.total_force_magnitude,
+            max_force_direction: value.max_force_direction,
+            max_force_magnitude: value.max_force_magnitude,
+        }
+    }
+}
+
+/// Event handler writing the raw events of a step into buffers kept by the solver.
+/// They are drained after the step without giving up their capacity, so delivering events doesn't allocate once warmed up.
+#[derive(Default)]
+pub struct EventBuffers {
+    collision_events: Mutex<Vec<CollisionEvent>>,
+    contact_force_events: Mutex<Vec<ContactForceEvent>>,
+}
+
+impl EventHandler for EventBuffers {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: CollisionEvent,
+        _contact_pair: Option<&ContactPair>,
+    ) {
+        self.collision_events.lock().unwrap().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        dt: Real,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        contact_pair: &ContactPair,
+        total_force_magnitude: Real,
+    ) {
+        let event = ContactForceEvent::from_contact_pair(dt, contact_pair, total_force_magnitude);
+        self.contact_force_events.lock().unwrap().push(event);
+    }
+}
+
+/// Key of a pair in `active_pairs`, the same whichever order the colliders are given in.
+/// Ordered by collider index, so stay events come out in the same order on every run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PairKey(ColliderHandle, ColliderHandle);
+
+impl Ord for PairKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let raw_parts = |key: &PairKey| (key.0.into_raw_parts(), key.1.into_raw_parts());
+        raw_parts(self).cmp(&raw_parts(other))
+    }
+}
+
+impl PartialOrd for PairKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn pair_key(collider1: ColliderHandle, collider2: ColliderHandle) -> PairKey {
+    if collider1.into_raw_parts() <= collider2.into_raw_parts() {
+        PairKey(collider1, collider2)
+    } else {
+        PairKey(collider2, collider1)
+    }
+}
+
+// Body type pairs in the order of the bits of a collision types mask:
+// dynamic-dynamic, dynamic-kinematic, dynamic-fixed, kinematic-kinematic, kinematic-fixed, fixed-fixed
+const COLLISION_TYPES: [ActiveCollisionTypes; 6] = [
+    ActiveCollisionTypes::DYNAMIC_DYNAMIC,
+    ActiveCollisionTypes::DYNAMIC_KINEMATIC,
+    ActiveCollisionTypes::DYNAMIC_FIXED,
+    ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+    ActiveCollisionTypes::KINEMATIC_FIXED,
+    ActiveCollisionTypes::FIXED_FIXED,
+];
+
+pub(crate) fn collision_types_from_mask(mask: u32) -> ActiveCollisionTypes {
+    COLLISION_TYPES
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .fold(ActiveCollisionTypes::empty(), |types, (_, t)| types | *t)
+}
+
+pub(crate) fn collision_types_to_mask(types: ActiveCollisionTypes) -> u32 {
+    COLLISION_TYPES
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| types.contains(**t))
+        .fold(0, |mask, (i, _)| mask | (1 << i))
+}
+
+/// Adds or removes `CONTACT_FORCE_EVENTS` depending on whether the threshold can ever be reached.
+pub(crate) fn contact_force_active_events(events: ActiveEvents, threshold: f32) -> ActiveEvents {
+    if threshold.is_finite() {
+        events | ActiveEvents::CONTACT_FORCE_EVENTS
+    } else {
+        events - ActiveEvents::CONTACT_FORCE_EVENTS
+    }
+}
+
+impl PhysicsSolverData {
+    /// Keeps what the events need to know about the colliders of a body that is about to be removed.
+    pub(crate) fn remember_removed_colliders(&mut self, handle: RigidBodyHandle) {
+        let Some(rb) = self.rigid_body_set.get(handle) else {
+            return;
+        };
+        for collider_handle in rb.colliders() {
+            if let Some(collider) = self.collider_set.get(*collider_handle) {
+                self.removed_colliders
+                    .insert(*collider_handle, (handle, collider.user_data as u64));
+            }
+        }
+    }
+
+    fn collider_body_and_user_id(&self, handle: ColliderHandle) -> (RigidBodyHandle, u64) {
+        if let Some(collider) = self.collider_set.get(handle) {
+            let body = collider.parent().unwrap_or(RigidBodyHandle::invalid());
+            (body, collider.user_data as u64)
+        } else {
+            self.removed_colliders
+                .get(&handle)
+                .copied()
+                .unwrap_or((RigidBodyHandle::invalid(), 0))
+        }
+    }
+
+    fn collision_event(
+        &self,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+        is_started: bool,
+        flags: CollisionEventFlags,
+        (contacts_start, contacts_count): (u32, u32),
+    ) -> SerializableCollisionEvent {
+        let (body1, user_id1) = self.collider_body_and_user_id(collider1);
+        let (body2, user_id2) = self.collider_body_and_user_id(collider2);
+        SerializableCollisionEvent {
+            collider1: collider1.into(),
+            collider2: collider2.into(),
+            is_started,
+            contacts_start,
+            contacts_count,
+            body1: body1.into(),
+            body2: body2.into(),
+            user_id1,
+            user_id2,
+            is_sensor: flags.contains(CollisionEventFlags::SENSOR),
+            removed: flags.contains(CollisionEventFlags::REMOVED),
+        }
+    }
+
+    /// Appends the contact points between two colliders to `collision_contacts`.
+    /// Returns the range they were written to, as a start index and count.
+    fn push_contact_points(
+        &mut self,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ) -> (u32, u32) {
+        let start = self.collision_contacts.len() as u32;
+        let Some(pair) = self.narrow_phase.contact_pair(collider1, collider2) else {
+            return (start, 0);
+        };
+        // The pair may store the colliders in the opposite order of the event
+        let flipped = pair.collider1 != collider1;
+        let Some(collider) = self.collider_set.get(collider1) else {
+            return (start, 0);
+        };
+        for manifold in &pair.manifolds {
+            let normal = if flipped {
+                -manifold.data.normal
+            } else {
+                manifold.data.normal
+            };
+            for contact in &manifold.points {
+                let local_point = if flipped {
+                    contact.local_p2
+                } else {
+                    contact.local_p1
+                };
+                self.collision_contacts.push(SerializableContactPoint {
+                    point: (collider.position() * local_point).coords,
+                    normal,
+                    depth: -contact.dist,
+                    impulse: contact.data.impulse,
+                });
+            }
+        }
+        (start, self.collision_contacts.len() as u32 - start)
+    }
+
+    /// Drains the collision events of the last step into `collision_events`, gathering the contact points of every started contact.
+    /// Also updates the set of touching pairs and emits a stay event for each pair that was already touching.
+    pub(crate) fn collect_collision_events(&mut self) {
+        self.collision_contacts.clear();
+        self.collision_events.clear();
+        self.started_pairs.clear();
+        let mut raw_events = mem::take(self.event_buffers.collision_events.get_mut().unwrap());
+        for collision_event in raw_events.drain(..) {
+            let collider1 = collision_event.collider1();
+            let collider2 = collision_event.collider2();
+            let key = pair_key(collider1, collider2);
+            let (started, flags) = match collision_event {
+                CollisionEvent::Started(_, _, flags) => (true, flags),
+                CollisionEvent::Stopped(_, _, flags) => (false, flags),
+            };
+            let contacts = if started {
+                self.active_pairs.insert(key);
+                self.started_pairs.insert(key);
+                self.push_contact_points(collider1, collider2)
+            } else {
+                self.active_pairs.remove(&key);
+                (self.collision_contacts.len() as u32, 0)
+            };
+            let event = self.collision_event(collider1, collider2, started, flags, contacts);
+            self.collision_events.push(event);
+        }
+        // Hand the emptied buffer back so the next step reuses its capacity
+        *self.event_buffers.collision_events.get_mut().unwrap() = raw_events;
+        self.collect_collision_stay_events();
+        // Their stop events were all reported by this step
+        self.removed_colliders.clear();
+    }
+
+    fn collect_collision_stay_events(&mut self) {
+        self.collision_stay_events.clear();
+        let colliders = &self.collider_set;
+        self.stay_events_disabled
+            .retain(|handle| colliders.contains(*handle));
+        // A pair can stop touching without a stop event, for instance when collision events get
+        // disabled while it touches, so the narrow phase decides which pairs are still touching
+        let narrow_phase = &self.narrow_phase;
+        self.active_pairs.retain(|PairKey(collider1, collider2)| {
+            narrow_phase.intersection_pair(*collider1, *collider2) == Some(true)
+                || narrow_phase
+                    .contact_pair(*collider1, *collider2)
+                    .is_some_and(|pair| pair.has_any_active_contact)
+        });
+
+        // Taken out for the loop, nothing below touches the set of touching pairs
+        let active_pairs = mem::take(&mut self.active_pairs);
+        for pair in &active_pairs {
+            let PairKey(collider1, collider2) = *pair;
+            if self.started_pairs.contains(pair)
+                || self.stay_events_disabled.contains(&collider1)
+                || self.stay_events_disabled.contains(&collider2)
+            {
+                continue;
+            }
+            let contacts = if self.stay_event_contacts {
+                self.push_contact_points(collider1, collider2)
+            } else {
+                (self.collision_contacts.len() as u32, 0)
+            };
+            let is_sensor = [collider1, collider2].iter().any(|handle| {
+                self.collider_set
+                    .get(*handle)
+                    .is_some_and(|collider| collider.is_sensor())
+            });
+            let flags = if is_sensor {
+                CollisionEventFlags::SENSOR
+            } else {
+                CollisionEventFlags::empty()
+            };
+            let event = self.collision_event(collider1, collider2, true, flags, contacts);
+            self.collision_stay_events.push(event);
+        }
+        self.active_pairs = active_pairs;
+    }
+
+    /// Drains the contact force events of the last step into `contact_force_events`.
+    pub(crate) fn collect_contact_force_events(&mut self) {
+        self.contact_force_events.clear();
+        let raw_events = self.event_buffers.contact_force_events.get_mut().unwrap();
+        self.contact_force_events.extend(
+            raw_events
+                .drain(..)
+                .map(SerializableContactForceEvent::from),
+        );
+    }
+}
+
+/// Contact points referenced by the events returned from the last `solve`.
+/// Borrowed from the solver, see `RawArray`.
+#[unsafe(no_mangle)]
+extern "C" fn get_collision_contacts() -> RawArray<SerializableContactPoint> {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return RawArray::empty();
+    };
+    let collision_contacts = &mut psd.collision_contacts;
+    RawArray {
+        ptr: collision_contacts.as_mut_ptr(),
+        len: collision_contacts.len(),
+        capacity: collision_contacts.capacity(),
+    }
+}
+
+/// Reports contact force events for the collider once the total contact force goes over `threshold`,
+/// pass infinity to stop reporting them.
+#[unsafe(no_mangle)]
+extern "C" fn set_collider_contact_force_event_threshold(
+    collider_handle: SerializableColliderHandle,
+    threshold: f32,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(collider) = psd.collider_set.get_mut(collider_handle.into()) else {
+        log::warn!(
+            "set_collider_contact_force_event_threshold: invalid collider handle {:?}",
+            collider_handle
+        );
+        return;
+    };
+    collider.set_active_events(contact_force_active_events(
+        collider.active_events(),
+        threshold,
+    ));
+    collider.set_contact_force_event_threshold(threshold);
+}
+
+/// Contact force events of the last `solve`, one per pair whose force went over a collider's threshold.
+/// Borrowed from the solver, see `RawArray`.
+#[unsafe(no_mangle)]
+extern "C" fn get_contact_force_events() -> RawArray<SerializableContactForceEvent> {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return RawArray::empty();
+    };
+    let contact_force_events = &mut psd.contact_force_events;
+    RawArray {
+        ptr: contact_force_events.as_mut_ptr(),
+        len: contact_force_events.len(),
+        capacity: contact_force_events.capacity(),
+    }
+}
+
+/// Pairs that were touching before the last `solve` and still are, the counterpart of
+/// `OnCollisionStay` and `OnTriggerStay`. `is_started` is always true, and the contact range
+/// is only filled in when `set_stay_event_contacts` is enabled.
+/// Borrowed from the solver, see `RawArray`.
+#[unsafe(no_mangle)]
+extern "C" fn get_collision_stay_events() -> RawArray<SerializableCollisionEvent> {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return RawArray::empty();
+    };
+    let collision_stay_events = &mut psd.collision_stay_events;
+    RawArray {
+        ptr: collision_stay_events.as_mut_ptr(),
+        len: collision_stay_events.len(),
+        capacity: collision_stay_events.capacity(),
+    }
+}
+
+/// Gathers contact points for stay events too, off by default as it is paid for every touching pair.
+#[unsafe(no_mangle)]
+extern "C" fn set_stay_event_contacts(enabled: bool) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.stay_event_contacts = enabled;
+}
+
+/// Stops emitting stay events for every pair involving this collider, started and stopped events are unaffected.
+#[unsafe(no_mangle)]
+extern "C" fn set_collider_stay_events(collider_handle: SerializableColliderHandle, enabled: bool) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    if !psd.collider_set.contains(collider_handle.into()) {
+        log::warn!(
+            "set_collider_stay_events: invalid collider handle {:?}",
+            collider_handle
+        );
+        return;
+    }
+    if enabled {
+        psd.stay_events_disabled.remove(&collider_handle.into());
+    } else {
+        psd.stay_events_disabled.insert(collider_handle.into());
+    }
+}
+
+/// Id reported with every event involving this collider, so C# can find its objects without a lookup.
+#[unsafe(no_mangle)]
+extern "C" fn set_collider_user_id(collider_handle: SerializableColliderHandle, user_id: u64) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(collider) = psd.collider_set.get_mut(collider_handle.into()) else {
+        log::warn!(
+            "set_collider_user_id: invalid collider handle {:?}",
+            collider_handle
+        );
+        return;
+    };
+    collider.user_data = user_id as u128;
+}
+
+// Collider event configuration
+
+/// Sets which events the collider generates, as `ActiveEvents` bits:
+/// 1 for collision events and 2 for contact force events. Pairs generate an event if either collider asks for it.
+/// Like the descriptor, contact force events follow the collider's threshold whatever bit 2 says,
+/// see `set_collider_contact_force_event_threshold`.
+#[unsafe(no_mangle)]
+extern "C" fn set_collider_active_events(collider_handle: SerializableColliderHandle, events: u32) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(collider) = psd.collider_set.get_mut(collider_handle.into()) else {
+        log::warn!(
+            "set_collider_active_events: invalid collider handle {:?}",
+            collider_handle
+        );
+        return;
+    };
+    collider.set_active_events(contact_force_active_events(
+        ActiveEvents::from_bits_truncate(events),
+        collider.contact_force_event_threshold(),
+    ));
+}
+
+/// Sets which body type pairs the collider can touch or intersect, bits 0 to 5 being
+/// dynamic-dynamic, dynamic-kinematic, dynamic-fixed, kinematic-kinematic, kinematic-fixed and fixed-fixed.
+/// A pair interacts if either collider allows it. Rapier only enables the pairs involving a dynamic body
+/// by default, Unity's trigger rules also need kinematic-kinematic and kinematic-fixed on sensors.
+#[unsafe(no_mangle)]
+extern "C" fn set_collider_active_collision_types(
+    collider_handle: SerializableColliderHandle,
+    collision_types: u32,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(collider) = psd.collider_set.get_mut(collider_handle.into()) else {
+        log::warn!(
+            "set_collider_active_collision_types: invalid collider handle {:?}",
+            collider_handle
+        );
+        return;
+    };
+    collider.set_active_collision_types(collision_types_from_mask(collision_types));
+}
@@ -72,6 +72,20 @@ impl From<RigidBodyType> for SerializableRigidBodyType {
     }
 }
 
+impl TryFrom<u32> for SerializableRigidBodyType {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SerializableRigidBodyType::Dynamic),
+            1 => Ok(SerializableRigidBodyType::Fixed),
+            2 => Ok(SerializableRigidBodyType::KinematicPositionBased),
+            3 => Ok(SerializableRigidBodyType::KinematicVelocityBased),
+            _ => Err(()),
+        }
+    }
+}
+
 impl From<SerializableRigidBodyType> for RigidBodyType {
     fn from(value: SerializableRigidBodyType) -> Self {
         match value {
@@ -110,3 +124,96 @@ impl From<SerializableImpulseJointHandle> for ImpulseJointHandle {
         ImpulseJointHandle::from_raw_parts(value.index, value.generation)
     }
 }
+
+// SerializableMultibodyJointHandle, shares its index with the rigid body of the child link
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct SerializableMultibodyJointHandle {
+    index: u32,
+    generation: u32,
+}
+
+impl From<MultibodyJointHandle> for SerializableMultibodyJointHandle {
+    fn from(value: MultibodyJointHandle) -> Self {
+        let val = value.into_raw_parts();
+        SerializableMultibodyJointHandle {
+            index: val.0,
+            generation: val.1,
+        }
+    }
+}
+
+impl From<SerializableMultibodyJointHandle> for MultibodyJointHandle {
+    fn from(value: SerializableMultibodyJointHandle) -> Self {
+        MultibodyJointHandle::from_raw_parts(value.index, value.generation)
+    }
+}
+
+// SerializableJointAxis, a degree of freedom of a joint in its local frame
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub enum SerializableJointAxis {
+    LinX = 0,
+    LinY = 1,
+    LinZ = 2,
+    AngX = 3,
+    AngY = 4,
+    AngZ = 5,
+}
+
+impl TryFrom<u32> for SerializableJointAxis {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SerializableJointAxis::LinX),
+            1 => Ok(SerializableJointAxis::LinY),
+            2 => Ok(SerializableJointAxis::LinZ),
+            3 => Ok(SerializableJointAxis::AngX),
+            4 => Ok(SerializableJointAxis::AngY),
+            5 => Ok(SerializableJointAxis::AngZ),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<SerializableJointAxis> for JointAxis {
+    fn from(value: SerializableJointAxis) -> Self {
+        match value {
+            SerializableJointAxis::LinX => JointAxis::LinX,
+            SerializableJointAxis::LinY => JointAxis::LinY,
+            SerializableJointAxis::LinZ => JointAxis::LinZ,
+            SerializableJointAxis::AngX => JointAxis::AngX,
+            SerializableJointAxis::AngY => JointAxis::AngY,
+            SerializableJointAxis::AngZ => JointAxis::AngZ,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub enum SerializableMotorModel {
+    AccelerationBased = 0,
+    ForceBased = 1,
+}
+
+impl TryFrom<u32> for SerializableMotorModel {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SerializableMotorModel::AccelerationBased),
+            1 => Ok(SerializableMotorModel::ForceBased),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<SerializableMotorModel> for MotorModel {
+    fn from(value: SerializableMotorModel) -> Self {
+        match value {
+            SerializableMotorModel::AccelerationBased => MotorModel::AccelerationBased,
+            SerializableMotorModel::ForceBased => MotorModel::ForceBased,
+        }
+    }
+}
@@ -0,0 +1,467 @@
+// Physics hooks, called by the narrow phase for colliders flagged with `ActiveHooks`.
+// Native surface behaviours run first, then pair filters and contact modification
+// can be forwarded to callbacks registered from C#.
+
+use crate::handles::{SerializableColliderHandle, SerializableRigidBodyHandle};
+use crate::{PhysicsSolverData, get_mutable_physics_solver};
+use rapier3d::na::Vector3;
+use rapier3d::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Returns a `ContactPairFilterResult`.
+type ContactPairFilterCallback = extern "C" fn(
+    collider1: SerializableColliderHandle,
+    collider2: SerializableColliderHandle,
+    body1: SerializableRigidBodyHandle,
+    body2: SerializableRigidBodyHandle,
+) -> u32;
+
+/// Returns non-zero when the pair can intersect.
+type IntersectionPairFilterCallback = extern "C" fn(
+    collider1: SerializableColliderHandle,
+    collider2: SerializableColliderHandle,
+    body1: SerializableRigidBodyHandle,
+    body2: SerializableRigidBodyHandle,
+) -> u32;
+
+/// Can edit the normal and the `count` contacts in place, setting `enabled` to 0 drops a contact.
+type SolverContactModifierCallback = extern "C" fn(
+    collider1: SerializableColliderHandle,
+    collider2: SerializableColliderHandle,
+    normal: *mut Vector3<f32>,
+    contacts: *mut SerializableSolverContact,
+    count: usize,
+);
+
+// What a contact pair filter callback decides for the pair.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy)]
+enum ContactPairFilterResult {
+    // No contacts are computed for the pair
+    Ignore = 0,
+    // Contacts are computed and solved, the default behaviour
+    ComputeImpulses = 1,
+    // Contacts are computed and reported but don't push the bodies apart
+    ContactsOnly = 2,
+}
+
+impl From<u32> for ContactPairFilterResult {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => ContactPairFilterResult::Ignore,
+            2 => ContactPairFilterResult::ContactsOnly,
+            // Unknown values keep the default behaviour
+            _ => ContactPairFilterResult::ComputeImpulses,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct SerializableSolverContact {
+    // World-space contact point
+    point: Vector3<f32>,
+    // Negative when penetrating
+    dist: f32,
+    friction: f32,
+    restitution: f32,
+    // Desired relative velocity along the surface, for conveyor belts
+    tangent_velocity: Vector3<f32>,
+    // Written by C#, so a plain integer rather than a bool, the contact is kept when non-zero
+    enabled: u32,
+}
+
+#[derive(Default)]
+struct HookCallbacks {
+    filter_contact_pair: Option<ContactPairFilterCallback>,
+    filter_intersection_pair: Option<IntersectionPairFilterCallback>,
+    modify_solver_contacts: Option<SolverContactModifierCallback>,
+}
+
+// Contact behaviours applied natively to every contact of a collider.
+#[derive(Debug, Default, Clone, Copy)]
+struct SurfaceBehaviour {
+    // Normal in the collider's local space that contacts are allowed along, and the allowed angle around it
+    one_way: Option<(Vector3<Real>, Real)>,
+    // Surface velocity in the collider's local space
+    conveyor: Option<Vector3<Real>>,
+}
+
+#[derive(Default)]
+pub struct SolverHooks {
+    callbacks: HookCallbacks,
+    // Hooks each collider forwards to the C# callbacks
+    managed_hooks: HashMap<ColliderHandle, ActiveHooks>,
+    surfaces: HashMap<ColliderHandle, SurfaceBehaviour>,
+    // Colliders each collider never touches nor intersects, stored both ways
+    ignored_pairs: HashMap<ColliderHandle, HashSet<ColliderHandle>>,
+    // Contacts handed to the modifier callback, reused across manifolds and steps
+    contact_buffer: Mutex<Vec<SerializableSolverContact>>,
+}
+
+fn body_handle(handle: Option<RigidBodyHandle>) -> SerializableRigidBodyHandle {
+    handle.unwrap_or(RigidBodyHandle::invalid()).into()
+}
+
+impl SolverHooks {
+    /// Whether either collider of the pair forwards `hook` to C#.
+    fn is_managed(
+        &self,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+        hook: ActiveHooks,
+    ) -> bool {
+        [collider1, collider2].iter().any(|handle| {
+            self.managed_hooks
+                .get(handle)
+                .is_some_and(|hooks| hooks.contains(hook))
+        })
+    }
+
+    /// Hooks the collider needs the narrow phase to call.
+    fn required_hooks(&self, handle: ColliderHandle) -> ActiveHooks {
+        let mut hooks = self
+            .managed_hooks
+            .get(&handle)
+            .copied()
+            .unwrap_or(ActiveHooks::empty());
+        if self.surfaces.contains_key(&handle) {
+            hooks |= ActiveHooks::MODIFY_SOLVER_CONTACTS;
+        }
+        if self.ignored_pairs.contains_key(&handle) {
+            hooks |= ActiveHooks::FILTER_CONTACT_PAIRS | ActiveHooks::FILTER_INTERSECTION_PAIR;
+        }
+        hooks
+    }
+
+    fn is_ignored(&self, collider1: ColliderHandle, collider2: ColliderHandle) -> bool {
+        self.ignored_pairs
+            .get(&collider1)
+            .is_some_and(|ignored| ignored.contains(&collider2))
+    }
+
+    fn set_ignored(&mut self, collider1: ColliderHandle, collider2: ColliderHandle, ignore: bool) {
+        for (handle, other) in [(collider1, collider2), (collider2, collider1)] {
+            if ignore {
+                self.ignored_pairs.entry(handle).or_default().insert(other);
+            } else if let Some(ignored) = self.ignored_pairs.get_mut(&handle) {
+                ignored.remove(&other);
+                if ignored.is_empty() {
+                    self.ignored_pairs.remove(&handle);
+                }
+            }
+        }
+    }
+
+    /// The allowed normal of a one-way surface on either collider, expressed as Rapier expects it:
+    /// in the first collider's local space, pointing from the first collider towards the second.
+    fn one_way_normal(
+        context: &ContactModificationContext,
+        normal: Vector3<Real>,
+        is_collider1: bool,
+    ) -> Option<Vector3<Real>> {
+        if is_collider1 {
+            return Some(normal);
+        }
+        let collider1 = context.colliders.get(context.collider1)?;
+        let collider2 = context.colliders.get(context.collider2)?;
+        Some(collider1.rotation().inverse() * collider2.rotation() * -normal)
+    }
+
+    fn apply_surfaces(&self, context: &mut ContactModificationContext) {
+        // The pair has a single `user_data` slot to track whether contacts are allowed, so only one
+        // one-way surface can drive it, the first collider's when both colliders are platforms
+        let mut one_way_applied = false;
+        for (handle, is_collider1) in [(context.collider1, true), (context.collider2, false)] {
+            let Some(surface) = self.surfaces.get(&handle) else {
+                continue;
+            };
+            if let Some((normal, allowed_angle)) = surface.one_way
+                && !one_way_applied
+            {
+                if let Some(allowed_normal) = Self::one_way_normal(context, normal, is_collider1) {
+                    context.update_as_oneway_platform(&allowed_normal, allowed_angle);
+                }
+                one_way_applied = true;
+            }
+            // The surface velocity is expressed from the first collider's point of view
+            let sign = if is_collider1 { 1.0 } else { -1.0 };
+            if let Some(velocity) = surface.conveyor {
+                let Some(collider) = context.colliders.get(handle) else {
+                    continue;
+                };
+                let tangent_velocity = collider.rotation() * velocity * sign;
+                for contact in context.solver_contacts.iter_mut() {
+                    contact.tangent_velocity = tangent_velocity;
+                }
+            }
+        }
+    }
+}
+
+impl PhysicsHooks for SolverHooks {
+    fn filter_contact_pair(&self, context: &PairFilterContext) -> Option<SolverFlags> {
+        if self.is_ignored(context.collider1, context.collider2) {
+            return None;
+        }
+        let Some(callback) = self.callbacks.filter_contact_pair else {
+            return Some(SolverFlags::COMPUTE_IMPULSES);
+        };
+        if !self.is_managed(
+            context.collider1,
+            context.collider2,
+            ActiveHooks::FILTER_CONTACT_PAIRS,
+        ) {
+            return Some(SolverFlags::COMPUTE_IMPULSES);
+        }
+        let result = callback(
+            context.collider1.into(),
+            context.collider2.into(),
+            body_handle(context.rigid_body1),
+            body_handle(context.rigid_body2),
+        );
+        match ContactPairFilterResult::from(result) {
+            ContactPairFilterResult::Ignore => None,
+            ContactPairFilterResult::ComputeImpulses => Some(SolverFlags::COMPUTE_IMPULSES),
+            ContactPairFilterResult::ContactsOnly => Some(SolverFlags::empty()),
+        }
+    }
+
+    fn filter_intersection_pair(&self, context: &PairFilterContext) -> bool {
+        if self.is_ignored(context.collider1, context.collider2) {
+            return false;
+        }
+        let Some(callback) = self.callbacks.filter_intersection_pair else {
+            return true;
+        };
+        if !self.is_managed(
+            context.collider1,
+            context.collider2,
+            ActiveHooks::FILTER_INTERSECTION_PAIR,
+        ) {
+            return true;
+        }
+        callback(
+            context.collider1.into(),
+            context.collider2.into(),
+            body_handle(context.rigid_body1),
+            body_handle(context.rigid_body2),
+        ) != 0
+    }
+
+    fn modify_solver_contacts(&self, context: &mut ContactModificationContext) {
+        self.apply_surfaces(context);
+        let Some(callback) = self.callbacks.modify_solver_contacts else {
+            return;
+        };
+        if !self.is_managed(
+            context.collider1,
+            context.collider2,
+            ActiveHooks::MODIFY_SOLVER_CONTACTS,
+        ) {
+            return;
+        }
+        let mut contacts = self.contact_buffer.lock().unwrap();
+        contacts.clear();
+        contacts.extend(
+            context
+                .solver_contacts
+                .iter()
+                .map(|contact| SerializableSolverContact {
+                    point: contact.point.coords,
+                    dist: contact.dist,
+                    friction: contact.friction,
+                    restitution: contact.restitution,
+                    tangent_velocity: contact.tangent_velocity,
+                    enabled: 1,
+                }),
+        );
+        callback(
+            context.collider1.into(),
+            context.collider2.into(),
+            context.normal,
+            contacts.as_mut_ptr(),
+            contacts.len(),
+        );
+        for (contact, modified) in context.solver_contacts.iter_mut().zip(contacts.iter()) {
+            contact.point = modified.point.into();
+            contact.dist = modified.dist;
+            contact.friction = modified.friction;
+            contact.restitution = modified.restitution;
+            contact.tangent_velocity = modified.tangent_velocity;
+        }
+        let mut enabled = contacts.iter().map(|contact| contact.enabled != 0);
+        context
+            .solver_contacts
+            .retain(|_| enabled.next().unwrap_or(true));
+    }
+}
+
+impl PhysicsSolverData {
+    /// Flags the collider with every hook it needs, whatever needed it.
+    pub(crate) fn refresh_active_hooks(&mut self, handle: ColliderHandle) {
+        let hooks = self.hooks.required_hooks(handle);
+        if let Some(collider) = self.collider_set.get_mut(handle) {
+            collider.set_active_hooks(hooks);
+        }
+    }
+
+    /// Drops everything the hooks keep for a collider that was removed.
+    pub(crate) fn forget_collider_hooks(&mut self, handle: ColliderHandle) {
+        self.hooks.managed_hooks.remove(&handle);
+        self.hooks.surfaces.remove(&handle);
+        let ignored = self.hooks.ignored_pairs.remove(&handle).unwrap_or_default();
+        for other in ignored {
+            self.hooks.set_ignored(handle, other, false);
+            self.refresh_active_hooks(other);
+        }
+    }
+
+    fn update_surface(
+        &mut self,
+        handle: ColliderHandle,
+        update: impl FnOnce(&mut SurfaceBehaviour),
+    ) {
+        let surface = self.hooks.surfaces.entry(handle).or_default();
+        update(surface);
+        if surface.one_way.is_none() && surface.conveyor.is_none() {
+            self.hooks.surfaces.remove(&handle);
+        }
+        self.refresh_active_hooks(handle);
+    }
+
+    pub(crate) fn set_managed_hooks(&mut self, handle: ColliderHandle, hooks: ActiveHooks) {
+        if hooks.is_empty() {
+            self.hooks.managed_hooks.remove(&handle);
+        } else {
+            self.hooks.managed_hooks.insert(handle, hooks);
+        }
+        self.refresh_active_hooks(handle);
+    }
+}
+
+/// Registers the C# functions the hooks forward to, any of them can be null.
+/// They are called from inside `solve` for pairs where a collider has the matching `set_collider_active_hooks` flag.
+/// The solver is mutably borrowed for the whole step, so the callbacks must not call back into any export of
+/// this library, queries included: such calls log an error and return as if given an invalid handle.
+/// Record what they need and act on it once `solve` returns.
+#[unsafe(no_mangle)]
+extern "C" fn set_physics_hook_callbacks(
+    filter_contact_pair: Option<ContactPairFilterCallback>,
+    filter_intersection_pair: Option<IntersectionPairFilterCallback>,
+    modify_solver_contacts: Option<SolverContactModifierCallback>,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.hooks.callbacks = HookCallbacks {
+        filter_contact_pair,
+        filter_intersection_pair,
+        modify_solver_contacts,
+    };
+}
+
+/// Sets which hooks the collider forwards to C#, as `ActiveHooks` bits:
+/// 1 to filter contact pairs, 2 to filter intersection pairs and 4 to modify solver contacts.
+#[unsafe(no_mangle)]
+extern "C" fn set_collider_active_hooks(collider_handle: SerializableColliderHandle, hooks: u32) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    if !psd.collider_set.contains(collider_handle.into()) {
+        log::warn!(
+            "set_collider_active_hooks: invalid collider handle {:?}",
+            collider_handle
+        );
+        return;
+    }
+    psd.set_managed_hooks(
+        collider_handle.into(),
+        ActiveHooks::from_bits_truncate(hooks),
+    );
+}
+
+// Surface behaviours
+
+/// Makes the collider a one-way platform, like Unity's `PlatformEffector2D`.
+/// Contacts are only kept when their normal is within `allowed_angle` radians of `local_normal`,
+/// given in the collider's local space, so bodies can pass through from the other side.
+/// A zero normal turns the platform back into a regular collider.
+#[unsafe(no_mangle)]
+extern "C" fn set_collider_one_way_platform(
+    collider_handle: SerializableColliderHandle,
+    local_normal: Vector3<f32>,
+    allowed_angle: f32,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    if !psd.collider_set.contains(collider_handle.into()) {
+        log::warn!(
+            "set_collider_one_way_platform: invalid collider handle {:?}",
+            collider_handle
+        );
+        return;
+    }
+    psd.update_surface(collider_handle.into(), |surface| {
+        surface.one_way = local_normal
+            .try_normalize(Real::EPSILON)
+            .map(|normal| (normal, allowed_angle));
+    });
+}
+
+/// Makes the collider a conveyor belt, carrying whatever touches it at `local_velocity`,
+/// given in the collider's local space. A zero velocity turns the conveyor off.
+#[unsafe(no_mangle)]
+extern "C" fn set_collider_conveyor(
+    collider_handle: SerializableColliderHandle,
+    local_velocity: Vector3<f32>,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    if !psd.collider_set.contains(collider_handle.into()) {
+        log::warn!(
+            "set_collider_conveyor: invalid collider handle {:?}",
+            collider_handle
+        );
+        return;
+    }
+    psd.update_surface(collider_handle.into(), |surface| {
+        surface.conveyor = (local_velocity != Vector3::zeros()).then_some(local_velocity);
+    });
+}
+
+// Pair filtering
+
+/// Stops the two colliders from touching or intersecting each other, like Unity's `Physics.IgnoreCollision`.
+/// Pass false to let them collide again. The pair is forgotten once either collider is removed.
+#[unsafe(no_mangle)]
+extern "C" fn ignore_collision_pair(
+    collider1: SerializableColliderHandle,
+    collider2: SerializableColliderHandle,
+    ignore: bool,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let handle1: ColliderHandle = collider1.into();
+    let handle2: ColliderHandle = collider2.into();
+    if !psd.collider_set.contains(handle1) || !psd.collider_set.contains(handle2) {
+        log::warn!("ignore_collision_pair: invalid collider handle");
+        return;
+    }
+    psd.hooks.set_ignored(handle1, handle2, ignore);
+    for handle in [handle1, handle2] {
+        psd.refresh_active_hooks(handle);
+        // Sleeping bodies would not notice the pair changed until something else wakes them
+        let parent = psd
+            .collider_set
+            .get(handle)
+            .and_then(|collider| collider.parent());
+        if let Some(rb) = parent.and_then(|parent| psd.rigid_body_set.get_mut(parent)) {
+            rb.wake_up(true);
+        }
+    }
+}
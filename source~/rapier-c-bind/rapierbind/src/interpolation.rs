@@ -0,0 +1,187 @@
+use crate::handles::SerializableRigidBodyHandle;
+use crate::readback::active_bodies;
+use crate::{PhysicsSolverData, RapierTransform, get_mutable_physics_solver};
+use rapier3d::na::Isometry3;
+use rapier3d::prelude::*;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct RapierTransformPair {
+    previous: RapierTransform,
+    current: RapierTransform,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct FixedStepInfo {
+    steps: u32,
+    alpha: f32,
+}
+
+/// Pose of a body before the step numbered `step`, stored in `previous_poses` at the index of its handle.
+/// A `resting` pose is where the body fell asleep, it stays valid until the body moves again.
+#[derive(Debug, Clone, Copy)]
+pub struct PreviousPose {
+    handle: RigidBodyHandle,
+    step: u64,
+    pose: Isometry3<Real>,
+    resting: bool,
+}
+
+impl Default for PreviousPose {
+    fn default() -> Self {
+        PreviousPose {
+            handle: RigidBodyHandle::invalid(),
+            step: 0,
+            pose: Isometry3::identity(),
+            resting: false,
+        }
+    }
+}
+
+impl PhysicsSolverData {
+    /// Remembers the pose of every awake body so it can be blended with the pose after the next step.
+    /// Sleeping and fixed bodies aren't moved by the step, so they are skipped, except the ones
+    /// that fell asleep during the last step: their pose is kept as resting in case a contact
+    /// wakes them in a later step, which happens after this runs.
+    /// Must be called before `begin_moved_bodies` replaces the bodies moved by the last step.
+    pub(crate) fn store_previous_poses(&mut self) {
+        self.step_count += 1;
+        let fell_asleep = self
+            .moved_bodies
+            .iter()
+            .map(|handle| RigidBodyHandle::from(*handle))
+            .filter(|handle| {
+                self.rigid_body_set
+                    .get(*handle)
+                    .is_some_and(|rb| rb.is_sleeping())
+            });
+        for handle in fell_asleep.chain(active_bodies(&self.island_manager).copied()) {
+            let Some(rb) = self.rigid_body_set.get(handle) else {
+                continue;
+            };
+            let index = handle.into_raw_parts().0 as usize;
+            if index >= self.previous_poses.len() {
+                self.previous_poses
+                    .resize(index + 1, PreviousPose::default());
+            }
+            self.previous_poses[index] = PreviousPose {
+                handle,
+                step: self.step_count,
+                pose: *rb.position(),
+                resting: rb.is_sleeping(),
+            };
+        }
+    }
+
+    /// Pose of the body before the last step, or its current pose if the last step didn't move it.
+    /// A body woken during the last step starts from where it fell asleep, if that was during a step.
+    /// Bodies put to sleep by a `Sleep` command have no resting pose and snap to their current pose instead.
+    fn previous_pose(&self, handle: RigidBodyHandle, rb: &RigidBody) -> Isometry<Real> {
+        match self.previous_poses.get(handle.into_raw_parts().0 as usize) {
+            Some(previous)
+                if previous.handle == handle
+                    && (previous.step == self.step_count
+                        || previous.resting && !rb.is_sleeping()) =>
+            {
+                previous.pose
+            }
+            _ => *rb.position(),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn get_transform_pair(rb_handle: SerializableRigidBodyHandle) -> RapierTransformPair {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return RapierTransformPair {
+            previous: (&Isometry3::identity()).into(),
+            current: (&Isometry3::identity()).into(),
+        };
+    };
+    let Some(rb) = psd.rigid_body_set.get(rb_handle.into()) else {
+        log::warn!(
+            "get_transform_pair: invalid rigid body handle {:?}",
+            rb_handle
+        );
+        let identity = (&Isometry3::identity()).into();
+        return RapierTransformPair {
+            previous: identity,
+            current: identity,
+        };
+    };
+    RapierTransformPair {
+        previous: (&psd.previous_pose(rb_handle.into(), rb)).into(),
+        current: rb.position().into(),
+    }
+}
+
+/// Blends the pose of the body before the last step with its current pose, `alpha` is clamped to 0..1.
+/// Bodies woken during the last step blend from where they fell asleep.
+#[unsafe(no_mangle)]
+extern "C" fn get_interpolated_transform(
+    rb_handle: SerializableRigidBodyHandle,
+    alpha: f32,
+) -> RapierTransform {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return (&Isometry3::identity()).into();
+    };
+    let Some(rb) = psd.rigid_body_set.get(rb_handle.into()) else {
+        log::warn!(
+            "get_interpolated_transform: invalid rigid body handle {:?}",
+            rb_handle
+        );
+        return (&Isometry3::identity()).into();
+    };
+    let previous = psd.previous_pose(rb_handle.into(), rb);
+    let current = rb.position();
+    let alpha = alpha.clamp(0.0, 1.0);
+    // Slerp is undefined for rotations exactly 180 degrees apart, snap to the closest pose instead
+    let interpolated = previous
+        .try_lerp_slerp(current, alpha, Real::EPSILON)
+        .unwrap_or(if alpha < 0.5 { previous } else { *current });
+    (&interpolated).into()
+}
+
+/// Feeds the frame delta time into the fixed-timestep accumulator.
+/// Returns how many physics steps to run this frame and the alpha to interpolate with afterwards.
+/// A `max_steps` of 0 means there is no cap on the number of steps.
+#[unsafe(no_mangle)]
+extern "C" fn advance_time_accumulator(frame_dt: f32, max_steps: u32) -> FixedStepInfo {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return FixedStepInfo {
+            steps: 0,
+            alpha: 0.0,
+        };
+    };
+    let dt = psd.integration_parameters.dt;
+    if dt <= 0.0 {
+        return FixedStepInfo {
+            steps: 0,
+            alpha: 0.0,
+        };
+    }
+
+    psd.time_accumulator += frame_dt.max(0.0);
+    let mut steps = (psd.time_accumulator / dt) as u32;
+    if max_steps > 0 && steps > max_steps {
+        // Drop the time we can't catch up on instead of spiralling further behind
+        steps = max_steps;
+        psd.time_accumulator %= dt;
+    } else {
+        psd.time_accumulator -= steps as f32 * dt;
+    }
+
+    FixedStepInfo {
+        steps,
+        alpha: (psd.time_accumulator / dt).clamp(0.0, 1.0),
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn reset_time_accumulator() {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.time_accumulator = 0.0;
+}
@@ -0,0 +1,500 @@
+use crate::descriptors::isometry_from_parts;
+use crate::handles::{
+    SerializableImpulseJointHandle, SerializableJointAxis, SerializableMotorModel,
+    SerializableRigidBodyHandle,
+};
+use crate::{PhysicsSolverData, RawArray, get_mutable_physics_solver};
+use rapier3d::math::SpatialVector;
+use rapier3d::na::{Vector3, Vector4};
+use rapier3d::prelude::*;
+
+// Motors
+
+/// Drives `axis` of the joint towards `target_pos` and `target_vel`, like Unity's `HingeJoint.motor`.
+/// Use `AngX` for revolute joints and `LinX` for prismatic joints.
+/// `axis` is a `SerializableJointAxis` and `model` a `SerializableMotorModel`, unknown values are rejected.
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_motor(
+    handle: SerializableImpulseJointHandle,
+    axis: u32,
+    target_pos: f32,
+    target_vel: f32,
+    stiffness: f32,
+    damping: f32,
+    max_force: f32,
+    model: u32,
+) {
+    let (Ok(axis), Ok(model)) = (
+        SerializableJointAxis::try_from(axis),
+        SerializableMotorModel::try_from(model),
+    ) else {
+        log::warn!(
+            "set_joint_motor: invalid axis {} or motor model {}",
+            axis,
+            model
+        );
+        return;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("set_joint_motor: invalid joint handle {:?}", handle);
+        return;
+    };
+    let axis: JointAxis = axis.into();
+    joint
+        .data
+        .set_motor(axis, target_pos, target_vel, stiffness, damping)
+        .set_motor_max_force(axis, max_force)
+        .set_motor_model(axis, model.into());
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn disable_joint_motor(handle: SerializableImpulseJointHandle, axis: u32) {
+    let Ok(axis) = SerializableJointAxis::try_from(axis) else {
+        log::warn!("disable_joint_motor: invalid axis {}", axis);
+        return;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("disable_joint_motor: invalid joint handle {:?}", handle);
+        return;
+    };
+    let axis: JointAxis = axis.into();
+    joint.data.motor_axes.remove(axis.into());
+    joint.data.motors[axis as usize] = JointMotor::default();
+}
+
+// Limits
+
+/// Limits `axis` of the joint to `[min, max]`, in radians for angular axes.
+/// `axis` is a `SerializableJointAxis`, unknown values are rejected.
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_limits(
+    handle: SerializableImpulseJointHandle,
+    axis: u32,
+    min: f32,
+    max: f32,
+) {
+    let Ok(axis) = SerializableJointAxis::try_from(axis) else {
+        log::warn!("set_joint_limits: invalid axis {}", axis);
+        return;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("set_joint_limits: invalid joint handle {:?}", handle);
+        return;
+    };
+    joint.data.set_limits(axis.into(), [min, max]);
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn disable_joint_limits(handle: SerializableImpulseJointHandle, axis: u32) {
+    let Ok(axis) = SerializableJointAxis::try_from(axis) else {
+        log::warn!("disable_joint_limits: invalid axis {}", axis);
+        return;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("disable_joint_limits: invalid joint handle {:?}", handle);
+        return;
+    };
+    let axis: JointAxis = axis.into();
+    joint.data.limit_axes.remove(axis.into());
+    joint.data.limits[axis as usize] = JointLimits::default();
+}
+
+/// Twist and swing limits in radians, matching Unity's `CharacterJoint`.
+/// Twist is around the joint's X axis, swing 1 and 2 are symmetric limits around its Y and Z axes.
+#[unsafe(no_mangle)]
+extern "C" fn set_twist_swing_limits(
+    handle: SerializableImpulseJointHandle,
+    twist_min: f32,
+    twist_max: f32,
+    swing1_limit: f32,
+    swing2_limit: f32,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("set_twist_swing_limits: invalid joint handle {:?}", handle);
+        return;
+    };
+    joint
+        .data
+        .set_limits(JointAxis::AngX, [twist_min, twist_max])
+        .set_limits(JointAxis::AngY, [-swing1_limit, swing1_limit])
+        .set_limits(JointAxis::AngZ, [-swing2_limit, swing2_limit]);
+}
+
+// Joint data
+// Every setter edits the joint in place and wakes both bodies, so the joint keeps its handle and solver state.
+
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_anchors(
+    handle: SerializableImpulseJointHandle,
+    local_anchor1: Vector3<f32>,
+    local_anchor2: Vector3<f32>,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("set_joint_anchors: invalid joint handle {:?}", handle);
+        return;
+    };
+    joint
+        .data
+        .set_local_anchor1(local_anchor1.into())
+        .set_local_anchor2(local_anchor2.into());
+}
+
+/// Sets the joint axis in the local space of each body, the X axis of both local frames.
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_axes(
+    handle: SerializableImpulseJointHandle,
+    local_axis1: Vector3<f32>,
+    local_axis2: Vector3<f32>,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("set_joint_axes: invalid joint handle {:?}", handle);
+        return;
+    };
+    joint
+        .data
+        .set_local_axis1(UnitVector::new_normalize(local_axis1))
+        .set_local_axis2(UnitVector::new_normalize(local_axis2));
+}
+
+/// Replaces both local frames, rotations are quaternions stored as x, y, z, w.
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_frames(
+    handle: SerializableImpulseJointHandle,
+    local_position1: Vector3<f32>,
+    local_rotation1: Vector4<f32>,
+    local_position2: Vector3<f32>,
+    local_rotation2: Vector4<f32>,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("set_joint_frames: invalid joint handle {:?}", handle);
+        return;
+    };
+    joint
+        .data
+        .set_local_frame1(isometry_from_parts(local_position1, local_rotation1))
+        .set_local_frame2(isometry_from_parts(local_position2, local_rotation2));
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_contacts_enabled(handle: SerializableImpulseJointHandle, enabled: bool) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!(
+            "set_joint_contacts_enabled: invalid joint handle {:?}",
+            handle
+        );
+        return;
+    };
+    joint.data.set_contacts_enabled(enabled);
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_enabled(handle: SerializableImpulseJointHandle, enabled: bool) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.impulse_joint_set.get_mut(handle.into(), true) else {
+        log::warn!("set_joint_enabled: invalid joint handle {:?}", handle);
+        return;
+    };
+    joint.data.set_enabled(enabled);
+}
+
+/// Moves the joint onto another pair of bodies, keeping its frames, limits, motors and break threshold.
+/// The joint graph can't rewire an edge, so the joint is reinserted and gets a new handle,
+/// which is returned. If either body is invalid the joint is left untouched and `handle` is returned.
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_bodies(
+    handle: SerializableImpulseJointHandle,
+    body1: SerializableRigidBodyHandle,
+    body2: SerializableRigidBodyHandle,
+) -> SerializableImpulseJointHandle {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    if !psd.rigid_body_set.contains(body1.into()) || !psd.rigid_body_set.contains(body2.into()) {
+        log::warn!("set_joint_bodies: invalid rigid body handle");
+        return handle;
+    }
+    let Some(joint) = psd.impulse_joint_set.remove(handle.into(), true) else {
+        log::warn!("set_joint_bodies: invalid joint handle {:?}", handle);
+        return ImpulseJointHandle::invalid().into();
+    };
+    let new_handle = psd
+        .impulse_joint_set
+        .insert(body1.into(), body2.into(), joint.data, true);
+    if let Some(threshold) = psd.joint_break_thresholds.remove(&handle.into()) {
+        psd.joint_break_thresholds.insert(new_handle, threshold);
+    }
+    new_handle.into()
+}
+
+// Breaking
+
+#[derive(Debug, Clone, Copy)]
+pub struct JointBreakThreshold {
+    force: f32,
+    torque: f32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SerializableJointBrokenEvent {
+    joint: SerializableImpulseJointHandle,
+    body1: SerializableRigidBodyHandle,
+    body2: SerializableRigidBodyHandle,
+    // Force and torque the joint applied during the step it broke
+    force: f32,
+    torque: f32,
+}
+
+/// Impulse the joint applied along each of its axes during the last substep, linear axes first.
+/// Rapier writes locked axes to `impulses` but limits and motors to their own slots,
+/// so ropes, springs and joints pushing against a limit only show up in the latter.
+fn joint_impulses(joint: &ImpulseJoint) -> SpatialVector<Real> {
+    let mut impulses = joint.impulses;
+    for (i, impulse) in impulses.iter_mut().enumerate() {
+        *impulse += joint.data.limits[i].impulse + joint.data.motors[i].impulse;
+    }
+    impulses
+}
+
+impl PhysicsSolverData {
+    /// Converts a joint impulse into a force.
+    /// Joints keep the impulse of the last solver substep, not the whole step.
+    fn joint_impulse_to_force(&self) -> Real {
+        let params = &self.integration_parameters;
+        params.inv_dt() * params.num_solver_iterations.get() as Real
+    }
+
+    /// Called after stepping, removes every joint whose last impulse exceeded its break threshold.
+    pub(crate) fn break_joints(&mut self) {
+        self.broken_joints.clear();
+        let impulse_to_force = self.joint_impulse_to_force();
+        let joints = &mut self.impulse_joint_set;
+        let broken_joints = &mut self.broken_joints;
+        self.joint_break_thresholds.retain(|handle, threshold| {
+            // Joints removed along with one of their bodies are dropped here
+            let Some(joint) = joints.get(*handle) else {
+                return false;
+            };
+            let impulses = joint_impulses(joint);
+            let force = impulses.fixed_rows::<3>(0).norm() * impulse_to_force;
+            let torque = impulses.fixed_rows::<3>(3).norm() * impulse_to_force;
+            if force <= threshold.force && torque <= threshold.torque {
+                return true;
+            }
+            broken_joints.push(SerializableJointBrokenEvent {
+                joint: (*handle).into(),
+                body1: joint.body1.into(),
+                body2: joint.body2.into(),
+                force,
+                torque,
+            });
+            joints.remove(*handle, true);
+            false
+        });
+    }
+}
+
+/// Breaks the joint once the force or torque it applies goes over the threshold,
+/// like Unity's `Joint.breakForce` and `Joint.breakTorque`. Pass infinity to never break.
+#[unsafe(no_mangle)]
+extern "C" fn set_joint_break_threshold(
+    handle: SerializableImpulseJointHandle,
+    break_force: f32,
+    break_torque: f32,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    if !psd.impulse_joint_set.contains(handle.into()) {
+        log::warn!(
+            "set_joint_break_threshold: invalid joint handle {:?}",
+            handle
+        );
+        return;
+    }
+    if break_force == f32::INFINITY && break_torque == f32::INFINITY {
+        psd.joint_break_thresholds.remove(&handle.into());
+    } else {
+        psd.joint_break_thresholds.insert(
+            handle.into(),
+            JointBreakThreshold {
+                force: break_force,
+                torque: break_torque,
+            },
+        );
+    }
+}
+
+/// Joints that broke during the last `solve`, the counterpart of `OnJointBreak`.
+/// Borrowed from the solver, see `RawArray`.
+#[unsafe(no_mangle)]
+extern "C" fn get_broken_joints() -> RawArray<SerializableJointBrokenEvent> {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return RawArray::empty();
+    };
+    let broken_joints = &mut psd.broken_joints;
+    RawArray {
+        ptr: broken_joints.as_mut_ptr(),
+        len: broken_joints.len(),
+        capacity: broken_joints.capacity(),
+    }
+}
+
+// State readback
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct SerializableJointState {
+    // Position of the second frame relative to the first, along LinX, LinY and LinZ
+    translation: Vector3<f32>,
+    // Rotation of the second frame relative to the first around AngX, AngY and AngZ, in radians
+    angles: Vector3<f32>,
+    // Relative velocities, in the axes of the first frame
+    linear_velocity: Vector3<f32>,
+    angular_velocity: Vector3<f32>,
+    // Applied to the second body during the last step in world space, the first body gets the opposite.
+    // Ropes and springs pull along the line between the anchors, but Rapier reports that on the first frame's X axis
+    force: Vector3<f32>,
+    torque: Vector3<f32>,
+}
+
+/// Reads the current state of a joint, the counterpart of `HingeJoint.angle`, `HingeJoint.velocity`
+/// and `Joint.currentForce`/`currentTorque`. Returns false if the joint doesn't exist or `out_state` is null.
+#[unsafe(no_mangle)]
+extern "C" fn get_joint_state(
+    handle: SerializableImpulseJointHandle,
+    out_state: *mut SerializableJointState,
+) -> bool {
+    if out_state.is_null() {
+        log::warn!("get_joint_state called with a null output state");
+        return false;
+    }
+    let Some(psd) = get_mutable_physics_solver() else {
+        return false;
+    };
+    let Some(joint) = psd.impulse_joint_set.get(handle.into()) else {
+        return false;
+    };
+    let (Some(rb1), Some(rb2)) = (
+        psd.rigid_body_set.get(joint.body1),
+        psd.rigid_body_set.get(joint.body2),
+    ) else {
+        return false;
+    };
+
+    let frame1 = rb1.position() * joint.data.local_frame1;
+    let frame2 = rb2.position() * joint.data.local_frame2;
+    let relative = frame1.inv_mul(&frame2);
+    let (roll, pitch, yaw) = relative.rotation.euler_angles();
+
+    let anchor1 = frame1.translation.vector.into();
+    let anchor2 = frame2.translation.vector.into();
+    let linvel = rb2.velocity_at_point(&anchor2) - rb1.velocity_at_point(&anchor1);
+    let angvel = rb2.angvel() - rb1.angvel();
+
+    let impulse_to_force = psd.joint_impulse_to_force();
+    let impulses = joint_impulses(joint);
+    let force = impulses.fixed_rows::<3>(0) * impulse_to_force;
+    let torque = impulses.fixed_rows::<3>(3) * impulse_to_force;
+
+    let state = SerializableJointState {
+        translation: relative.translation.vector,
+        angles: vector![roll, pitch, yaw],
+        linear_velocity: frame1.rotation.inverse_transform_vector(&linvel),
+        angular_velocity: frame1.rotation.inverse_transform_vector(&angvel),
+        force: frame1.rotation * force,
+        torque: frame1.rotation * torque,
+    };
+    unsafe {
+        *out_state = state;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fixed anchor and a one kilogram ball hanging `distance` below it, tied by a rope.
+    fn hanging_rope(psd: &mut PhysicsSolverData, distance: f32) -> ImpulseJointHandle {
+        let anchor = psd.rigid_body_set.insert(RigidBodyBuilder::fixed());
+        let ball = psd
+            .rigid_body_set
+            .insert(RigidBodyBuilder::dynamic().translation(vector![0.0, -distance, 0.0]));
+        psd.collider_set.insert_with_parent(
+            ColliderBuilder::ball(0.1).mass(1.0),
+            ball,
+            &mut psd.rigid_body_set,
+        );
+        psd.impulse_joint_set
+            .insert(anchor, ball, RopeJointBuilder::new(1.0), true)
+    }
+
+    #[test]
+    fn stretched_rope_breaks() {
+        let mut psd = PhysicsSolverData::default();
+        let rope = hanging_rope(&mut psd, 1.5);
+        // Only the rope's limit holds the ball's weight, locked-axis impulses stay at zero
+        psd.joint_break_thresholds.insert(
+            rope,
+            JointBreakThreshold {
+                force: 5.0,
+                torque: f32::INFINITY,
+            },
+        );
+        psd.solve();
+        assert_eq!(psd.broken_joints.len(), 1);
+        assert!(psd.broken_joints[0].force > 5.0);
+        assert!(!psd.impulse_joint_set.contains(rope));
+    }
+
+    #[test]
+    fn taut_rope_holds_under_its_threshold() {
+        let mut psd = PhysicsSolverData::default();
+        let rope = hanging_rope(&mut psd, 1.0);
+        psd.joint_break_thresholds.insert(
+            rope,
+            JointBreakThreshold {
+                force: 100.0,
+                torque: f32::INFINITY,
+            },
+        );
+        for _ in 0..50 {
+            psd.solve();
+            assert!(psd.broken_joints.is_empty());
+        }
+        // The rope carries the ball's weight
+        let impulses = joint_impulses(psd.impulse_joint_set.get(rope).unwrap());
+        let force = impulses.fixed_rows::<3>(0).norm() * psd.joint_impulse_to_force();
+        assert!((force - 9.81).abs() < 1.0, "rope force {force}");
+    }
+}
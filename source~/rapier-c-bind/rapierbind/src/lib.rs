@@ -1,23 +1,67 @@
+mod commands;
+mod descriptors;
+mod events;
 mod handles;
+mod hooks;
+mod interpolation;
+mod joints;
+mod multibody;
+mod readback;
+mod transform_stream;
 mod utils;
 use crate::handles::{
     SerializableColliderHandle, SerializableRigidBodyHandle, SerializableRigidBodyType,
 };
+use descriptors::{
+    ColliderDescriptor, ColliderShape, IntegrationParametersDescriptor, JointDescriptor, JointType,
+    RigidBodyDescriptor,
+};
+use events::{
+    EventBuffers, PairKey, SerializableCollisionEvent, SerializableContactForceEvent,
+    SerializableContactPoint,
+};
 use handles::SerializableImpulseJointHandle;
-use rapier3d::crossbeam;
-use rapier3d::na::{Isometry, Quaternion, UnitQuaternion, Vector2, Vector3, Vector4};
+use hooks::SolverHooks;
+use interpolation::PreviousPose;
+use joints::{JointBreakThreshold, SerializableJointBrokenEvent};
+use rapier3d::na::{Isometry, Isometry3, Quaternion, UnitQuaternion, Vector2, Vector3, Vector4};
 use rapier3d::prelude::*;
-use std::mem;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, Ordering};
+use transform_stream::TransformStream;
 use unitybridge::{AssignUnityLogger, IUnityLog};
 use utils::{
     cancel_axis_velocity, locked_axes_to_unity_constraints, unity_constraints_to_locked_axes,
 };
 
 static mut PHYSIC_SOLVER_DATA: Option<PhysicsSolverData> = None;
+// Set while `solve` runs the pipeline step, which borrows the solver data and calls the hook callbacks.
+static STEPPING: AtomicBool = AtomicBool::new(false);
+
+/// Whether the caller runs inside a step, from a hook callback, where reaching the solver data
+/// would alias the step's borrow. Logs the export it was called from when it does.
+#[track_caller]
+fn called_during_step() -> bool {
+    let stepping = STEPPING.load(Ordering::Relaxed);
+    if stepping {
+        log::error!(
+            "{}: exports can't be called from a physics hook callback",
+            Location::caller()
+        );
+    }
+    stepping
+}
 
+/// The solver data for an export, `None` when called from a hook callback during `solve`.
+/// Exports then return the same value as for an invalid handle.
 #[allow(static_mut_refs)]
-fn get_mutable_physics_solver() -> &'static mut PhysicsSolverData<'static> {
-    unsafe { PHYSIC_SOLVER_DATA.as_mut().unwrap() }
+#[track_caller]
+fn get_mutable_physics_solver() -> Option<&'static mut PhysicsSolverData> {
+    if called_during_step() {
+        return None;
+    }
+    Some(unsafe { PHYSIC_SOLVER_DATA.as_mut().unwrap() })
 }
 
 #[repr(C)]
@@ -27,6 +71,9 @@ struct FunctionsToCallFromRust {
 
 #[unsafe(no_mangle)]
 extern "C" fn init(funcs: *const FunctionsToCallFromRust) {
+    if called_during_step() {
+        return;
+    }
     unsafe {
         PHYSIC_SOLVER_DATA = Some(PhysicsSolverData::default());
         AssignUnityLogger((*funcs).unity_log_ptr);
@@ -41,11 +88,17 @@ extern "C" fn hello_world() {
 // teardown
 #[unsafe(no_mangle)]
 extern "C" fn teardown() {
+    if called_during_step() {
+        return;
+    }
     unsafe {
         PHYSIC_SOLVER_DATA = None;
     }
 }
 
+/// View of a list owned by the solver, returned by `solve` and the per-step event getters.
+/// The solver refills the list in place on every step, so the view stays valid until the next
+/// `solve` or `teardown` and must not be freed by the caller.
 #[repr(C)]
 struct RawArray<T> {
     ptr: *mut T,
@@ -53,6 +106,18 @@ struct RawArray<T> {
     capacity: usize,
 }
 
+impl<T> RawArray<T> {
+    /// Returned by the getters when the solver can't be reached.
+    fn empty() -> Self {
+        RawArray {
+            ptr: std::ptr::null_mut(),
+            len: 0,
+            capacity: 0,
+        }
+    }
+}
+
+/// Steps the simulation and returns its collision events, borrowed from the solver like every `RawArray`.
 #[unsafe(no_mangle)]
 #[allow(static_mut_refs)]
 extern "C" fn solve() -> *const RawArray<SerializableCollisionEvent> {
@@ -63,37 +128,40 @@ extern "C" fn solve() -> *const RawArray<SerializableCollisionEvent> {
         }
     }
 
-    let mut collision_events = get_mutable_physics_solver().solve();
-    // box the vector to prevent it from being deallocated
-    let ptr = collision_events.as_mut_ptr();
-    let len = collision_events.len();
-    let capacity = collision_events.capacity();
-    let val = Box::new(RawArray { ptr, len, capacity });
-    mem::forget(collision_events);
-    Box::into_raw(val)
+    let Some(psd) = get_mutable_physics_solver() else {
+        return std::ptr::null();
+    };
+    psd.solve();
+    psd.collision_events_view = RawArray {
+        ptr: psd.collision_events.as_mut_ptr(),
+        len: psd.collision_events.len(),
+        capacity: psd.collision_events.capacity(),
+    };
+    &psd.collision_events_view
 }
 
+/// Deprecated, does nothing. `solve` now returns an array borrowed from the solver, see `RawArray`,
+/// so there is nothing left to free. Only kept so callers built against older bindings still link.
 #[unsafe(no_mangle)]
-extern "C" fn free_collision_events(ptr: *mut RawArray<SerializableCollisionEvent>) {
-    unsafe {
-        let info = Box::from_raw(ptr);
-        let _ = Vec::from_raw_parts(info.ptr, info.len, info.capacity);
-    }
-}
+extern "C" fn free_collision_events(_ptr: *mut RawArray<SerializableCollisionEvent>) {}
 
 // Settings
 
 #[unsafe(no_mangle)]
 extern "C" fn set_gravity(x: f32, y: f32, z: f32) {
-    get_mutable_physics_solver().gravity = vector![x, y, z];
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.gravity = vector![x, y, z];
 }
 
 #[unsafe(no_mangle)]
 extern "C" fn set_time_step(dt: f32) {
-    get_mutable_physics_solver().integration_parameters.dt = dt;
-    get_mutable_physics_solver()
-        .integration_parameters
-        .min_ccd_dt = dt / 100.0;
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.integration_parameters.dt = dt;
+    psd.integration_parameters.min_ccd_dt = dt / 100.0;
 }
 
 // Collider
@@ -106,13 +174,16 @@ extern "C" fn add_cuboid_collider(
     mass: f32,
     is_sensor: bool,
 ) -> SerializableColliderHandle {
-    let psd = get_mutable_physics_solver();
-    let collider = ColliderBuilder::cuboid(half_extents_x, half_extents_y, half_extents_z)
-        .active_events(ActiveEvents::COLLISION_EVENTS)
-        .density(mass)
-        .sensor(is_sensor)
-        .build();
-    psd.collider_set.insert(collider).into()
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ColliderHandle::invalid().into();
+    };
+    psd.insert_collider(&ColliderDescriptor {
+        shape: ColliderShape::Cuboid as u32,
+        half_extents: vector![half_extents_x, half_extents_y, half_extents_z],
+        density: mass,
+        is_sensor: is_sensor.into(),
+        ..Default::default()
+    })
 }
 
 #[unsafe(no_mangle)]
@@ -121,13 +192,16 @@ extern "C" fn add_sphere_collider(
     mass: f32,
     is_sensor: bool,
 ) -> SerializableColliderHandle {
-    let psd = get_mutable_physics_solver();
-    let collider = ColliderBuilder::ball(radius)
-        .density(mass)
-        .active_events(ActiveEvents::COLLISION_EVENTS)
-        .sensor(is_sensor)
-        .build();
-    psd.collider_set.insert(collider).into()
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ColliderHandle::invalid().into();
+    };
+    psd.insert_collider(&ColliderDescriptor {
+        shape: ColliderShape::Sphere as u32,
+        radius,
+        density: mass,
+        is_sensor: is_sensor.into(),
+        ..Default::default()
+    })
 }
 
 #[unsafe(no_mangle)]
@@ -137,13 +211,17 @@ extern "C" fn add_capsule_collider(
     mass: f32,
     is_sensor: bool,
 ) -> SerializableColliderHandle {
-    let psd = get_mutable_physics_solver();
-    let collider = ColliderBuilder::capsule_y(half_height, radius)
-        .density(mass)
-        .active_events(ActiveEvents::COLLISION_EVENTS)
-        .sensor(is_sensor)
-        .build();
-    psd.collider_set.insert(collider).into()
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ColliderHandle::invalid().into();
+    };
+    psd.insert_collider(&ColliderDescriptor {
+        shape: ColliderShape::Capsule as u32,
+        half_height,
+        radius,
+        density: mass,
+        is_sensor: is_sensor.into(),
+        ..Default::default()
+    })
 }
 
 // TODO Investigate optimizing this a bit
@@ -156,44 +234,19 @@ extern "C" fn add_mesh_collider(
     mass: f32,
     is_sensor: bool,
 ) -> SerializableColliderHandle {
-    let psd = get_mutable_physics_solver();
-
-    // Convert C arrays to Rust slices
-    let vertices_flat = unsafe { std::slice::from_raw_parts(vertices_ptr, vertices_count * 3) };
-    let indices_flat = unsafe { std::slice::from_raw_parts(indices_ptr, indices_count * 3) };
-
-    // Convert flat arrays to points
-    let mut vertices = Vec::with_capacity(vertices_count);
-    for i in 0..vertices_count {
-        vertices.push(point![
-            vertices_flat[i * 3],
-            vertices_flat[i * 3 + 1],
-            vertices_flat[i * 3 + 2]
-        ]);
-    }
-
-    // Convert flat indices to triangle indices
-    let mut indices = Vec::with_capacity(indices_count);
-    for i in 0..indices_count {
-        indices.push([
-            indices_flat[i * 3],
-            indices_flat[i * 3 + 1],
-            indices_flat[i * 3 + 2],
-        ]);
-    }
-
-    // Build the trimesh collider
-    if let Ok(collider_builder) = ColliderBuilder::trimesh(vertices, indices) {
-        let collider = collider_builder
-            .active_events(ActiveEvents::COLLISION_EVENTS)
-            .density(mass)
-            .sensor(is_sensor)
-            .build();
-        psd.collider_set.insert(collider).into()
-    } else {
-        log::warn!("Failed to create mesh collider");
-        ColliderHandle::invalid().into()
-    }
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ColliderHandle::invalid().into();
+    };
+    psd.insert_collider(&ColliderDescriptor {
+        shape: ColliderShape::Mesh as u32,
+        vertices_ptr,
+        vertices_count,
+        indices_ptr,
+        indices_count,
+        density: mass,
+        is_sensor: is_sensor.into(),
+        ..Default::default()
+    })
 }
 
 // TODO Investigate optimizing this a bit
@@ -205,33 +258,17 @@ extern "C" fn add_convex_mesh_collider(
     mass: f32,
     is_sensor: bool,
 ) -> SerializableColliderHandle {
-    let psd = get_mutable_physics_solver();
-
-    // Convert C arrays to Rust slices
-    let vertices_flat = unsafe { std::slice::from_raw_parts(vertices_ptr, vertices_count * 3) };
-
-    // Convert flat arrays to points
-    let mut points = Vec::with_capacity(vertices_count);
-    for i in 0..vertices_count {
-        points.push(point![
-            vertices_flat[i * 3],
-            vertices_flat[i * 3 + 1],
-            vertices_flat[i * 3 + 2]
-        ]);
-    }
-
-    // Build the convex hull collider
-    if let Some(collider_builder) = ColliderBuilder::convex_hull(&points) {
-        let collider = collider_builder
-            .active_events(ActiveEvents::COLLISION_EVENTS)
-            .density(mass)
-            .sensor(is_sensor)
-            .build();
-        psd.collider_set.insert(collider).into()
-    } else {
-        log::warn!("Failed to create convex hull collider");
-        ColliderHandle::invalid().into()
-    }
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ColliderHandle::invalid().into();
+    };
+    psd.insert_collider(&ColliderDescriptor {
+        shape: ColliderShape::ConvexMesh as u32,
+        vertices_ptr,
+        vertices_count,
+        density: mass,
+        is_sensor: is_sensor.into(),
+        ..Default::default()
+    })
 }
 
 // RigidBody
@@ -248,42 +285,24 @@ extern "C" fn add_rigid_body(
     rotation_z: f32,
     rotation_w: f32,
 ) -> SerializableRigidBodyHandle {
-    let psd = get_mutable_physics_solver();
-    let quat = Quaternion::new(rotation_w, rotation_x, rotation_y, rotation_z);
-
-    // Convert to unit quaternion
-    let unit_quat = UnitQuaternion::from_quaternion(quat);
-
-    // Extract the rotation angle and axis
-    let angle = unit_quat.angle();
-    let axis = unit_quat.axis().unwrap_or(Vector3::z_axis());
-
-    // Create the AngVector (axis-angle representation)
-    let ang_vector = axis.into_inner() * angle;
-
-    // Build with the AngVector
-    let rigid_body = RigidBodyBuilder::new(rb_type.into())
-        .translation(vector![position_x, position_y, position_z])
-        .rotation(ang_vector)
-        .build();
-
-    let rb_handle = psd.rigid_body_set.insert(rigid_body);
-    psd.collider_set
-        .set_parent(collider.into(), Some(rb_handle), &mut psd.rigid_body_set);
-    rb_handle.into()
+    let Some(psd) = get_mutable_physics_solver() else {
+        return RigidBodyHandle::invalid().into();
+    };
+    psd.insert_rigid_body(&RigidBodyDescriptor {
+        body_type: rb_type as u32,
+        collider,
+        position: vector![position_x, position_y, position_z],
+        rotation: Vector4::new(rotation_x, rotation_y, rotation_z, rotation_w),
+        ..Default::default()
+    })
 }
 
 #[unsafe(no_mangle)]
 extern "C" fn remove_rigid_body(rb_handle: SerializableRigidBodyHandle) {
-    let psd = get_mutable_physics_solver();
-    psd.rigid_body_set.remove(
-        rb_handle.into(),
-        &mut psd.island_manager,
-        &mut psd.collider_set,
-        &mut psd.impulse_joint_set,
-        &mut psd.multibody_joint_set,
-        true,
-    );
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.remove_rigid_body(rb_handle.into());
 }
 
 #[unsafe(no_mangle)]
@@ -295,7 +314,9 @@ extern "C" fn update_rigid_body_properties(
     linear_drag: f32,
     angular_drag: f32,
 ) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     let rb = psd.rigid_body_set.get_mut(rb_handle.into()).unwrap();
 
     // Update body type if different
@@ -331,25 +352,18 @@ extern "C" fn add_fixed_joint(
     local_frame2_z: f32,
     self_collision: bool,
 ) -> SerializableImpulseJointHandle {
-    let psd = get_mutable_physics_solver();
-    let point1: Point<Real> = point![local_frame1_x, local_frame1_y, local_frame1_z];
-    let point2: Point<Real> = point![local_frame2_x, local_frame2_y, local_frame2_z];
-    let anchor_rb = psd.rigid_body_set.get(rb1_handle.into()).unwrap();
-    let mover_rb = psd.rigid_body_set.get(rb2_handle.into()).unwrap();
-    // Construct the local_frame for the first body
-    let local_frame1 =
-        Isometry::from_parts(Translation::from(point1), anchor_rb.position().rotation);
-    // Construct the local_frame for the second body
-    let local_frame2 =
-        Isometry::from_parts(Translation::from(point2), mover_rb.position().rotation);
-    let joint = FixedJointBuilder::new()
-        .local_frame1(local_frame1)
-        .local_frame2(local_frame2)
-        .contacts_enabled(self_collision);
-
-    psd.impulse_joint_set
-        .insert(rb1_handle.into(), rb2_handle.into(), joint, false)
-        .into()
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    psd.insert_joint(&JointDescriptor {
+        joint_type: JointType::Fixed as u32,
+        body1: rb1_handle,
+        body2: rb2_handle,
+        local_anchor1: vector![local_frame1_x, local_frame1_y, local_frame1_z],
+        local_anchor2: vector![local_frame2_x, local_frame2_y, local_frame2_z],
+        contacts_enabled: self_collision.into(),
+        ..Default::default()
+    })
 }
 
 #[unsafe(no_mangle)]
@@ -364,27 +378,18 @@ extern "C" fn add_spherical_joint(
     local_frame2_z: f32,
     self_collision: bool,
 ) -> SerializableImpulseJointHandle {
-    let psd = get_mutable_physics_solver();
-    let point1: Point<Real> = point![local_frame1_x, local_frame1_y, local_frame1_z];
-    let point2: Point<Real> = point![local_frame2_x, local_frame2_y, local_frame2_z];
-    let anchor_rb = psd.rigid_body_set.get(rb1_handle.into()).unwrap();
-    let mover_rb = psd.rigid_body_set.get(rb2_handle.into()).unwrap();
-    // Construct the local_frame for the first body
-    let local_frame1 =
-        Isometry::from_parts(Translation::from(point1), anchor_rb.position().rotation);
-    // Construct the local_frame for the second body
-    let local_frame2 =
-        Isometry::from_parts(Translation::from(point2), mover_rb.position().rotation);
-    let joint = SphericalJointBuilder::new()
-        // .local_anchor1(point1)
-        // .local_anchor2(point2)
-        .local_frame1(local_frame1)
-        .local_frame2(local_frame2)
-        .contacts_enabled(self_collision);
-
-    psd.impulse_joint_set
-        .insert(rb1_handle.into(), rb2_handle.into(), joint, false)
-        .into()
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    psd.insert_joint(&JointDescriptor {
+        joint_type: JointType::Spherical as u32,
+        body1: rb1_handle,
+        body2: rb2_handle,
+        local_anchor1: vector![local_frame1_x, local_frame1_y, local_frame1_z],
+        local_anchor2: vector![local_frame2_x, local_frame2_y, local_frame2_z],
+        contacts_enabled: self_collision.into(),
+        ..Default::default()
+    })
 }
 
 #[unsafe(no_mangle)]
@@ -402,18 +407,19 @@ extern "C" fn add_revolute_joint(
     local_frame2_z: f32,
     self_collision: bool,
 ) -> SerializableImpulseJointHandle {
-    let psd = get_mutable_physics_solver();
-    let point1: Point<Real> = point![local_frame1_x, local_frame1_y, local_frame1_z];
-    let point2: Point<Real> = point![local_frame2_x, local_frame2_y, local_frame2_z];
-    let axis: UnitVector<Real> = UnitVector::new_normalize(vector![axis_x, axis_y, axis_z]);
-    let joint = RevoluteJointBuilder::new(axis)
-        .local_anchor1(point1)
-        .local_anchor2(point2)
-        .contacts_enabled(self_collision);
-
-    psd.impulse_joint_set
-        .insert(rb1_handle.into(), rb2_handle.into(), joint, false)
-        .into()
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    psd.insert_joint(&JointDescriptor {
+        joint_type: JointType::Revolute as u32,
+        body1: rb1_handle,
+        body2: rb2_handle,
+        axis: vector![axis_x, axis_y, axis_z],
+        local_anchor1: vector![local_frame1_x, local_frame1_y, local_frame1_z],
+        local_anchor2: vector![local_frame2_x, local_frame2_y, local_frame2_z],
+        contacts_enabled: self_collision.into(),
+        ..Default::default()
+    })
 }
 
 #[unsafe(no_mangle)]
@@ -433,37 +439,113 @@ extern "C" fn add_prismatic_joint(
     limit_max: f32,
     self_collision: bool,
 ) -> SerializableImpulseJointHandle {
-    let psd = get_mutable_physics_solver();
-    let point1: Point<Real> = point![local_frame1_x, local_frame1_y, local_frame1_z];
-    let point2: Point<Real> = point![local_frame2_x, local_frame2_y, local_frame2_z];
-    let axis: UnitVector<Real> = UnitVector::new_normalize(vector![axis_x, axis_y, axis_z]);
-    let joint = PrismaticJointBuilder::new(axis)
-        .local_anchor1(point1)
-        .local_anchor2(point2)
-        .limits([limit_min, limit_max])
-        // If the anchor is kinematic, then don't collide with the other body
-        .contacts_enabled(self_collision);
-
-    psd.impulse_joint_set
-        .insert(rb1_handle.into(), rb2_handle.into(), joint, false)
-        .into()
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    psd.insert_joint(&JointDescriptor {
+        joint_type: JointType::Prismatic as u32,
+        body1: rb1_handle,
+        body2: rb2_handle,
+        axis: vector![axis_x, axis_y, axis_z],
+        local_anchor1: vector![local_frame1_x, local_frame1_y, local_frame1_z],
+        local_anchor2: vector![local_frame2_x, local_frame2_y, local_frame2_z],
+        limits_enabled: 1,
+        limit_min,
+        limit_max,
+        contacts_enabled: self_collision.into(),
+        ..Default::default()
+    })
+}
+
+/// Spring pulling the anchors towards `rest_length` apart.
+/// `min_limit` and `max_limit` are hard limits on the distance between the anchors on top of the spring,
+/// unlike Unity's `minDistance` and `maxDistance` which only bound where the spring applies no force.
+/// A limit of 0 leaves the distance unbounded on that side.
+#[unsafe(no_mangle)]
+extern "C" fn add_spring_joint(
+    rb1_handle: SerializableRigidBodyHandle,
+    rb2_handle: SerializableRigidBodyHandle,
+    local_anchor1_x: f32,
+    local_anchor1_y: f32,
+    local_anchor1_z: f32,
+    local_anchor2_x: f32,
+    local_anchor2_y: f32,
+    local_anchor2_z: f32,
+    rest_length: f32,
+    stiffness: f32,
+    damping: f32,
+    min_limit: f32,
+    max_limit: f32,
+    self_collision: bool,
+) -> SerializableImpulseJointHandle {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    psd.insert_joint(&JointDescriptor {
+        joint_type: JointType::Spring as u32,
+        body1: rb1_handle,
+        body2: rb2_handle,
+        local_anchor1: vector![local_anchor1_x, local_anchor1_y, local_anchor1_z],
+        local_anchor2: vector![local_anchor2_x, local_anchor2_y, local_anchor2_z],
+        limits_enabled: (min_limit > 0.0 || max_limit > 0.0).into(),
+        limit_min: min_limit.max(0.0),
+        limit_max: if max_limit > 0.0 {
+            max_limit
+        } else {
+            Real::MAX
+        },
+        rest_length,
+        stiffness,
+        damping,
+        contacts_enabled: self_collision.into(),
+        ..Default::default()
+    })
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn add_rope_joint(
+    rb1_handle: SerializableRigidBodyHandle,
+    rb2_handle: SerializableRigidBodyHandle,
+    local_anchor1_x: f32,
+    local_anchor1_y: f32,
+    local_anchor1_z: f32,
+    local_anchor2_x: f32,
+    local_anchor2_y: f32,
+    local_anchor2_z: f32,
+    max_distance: f32,
+    self_collision: bool,
+) -> SerializableImpulseJointHandle {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return ImpulseJointHandle::invalid().into();
+    };
+    psd.insert_joint(&JointDescriptor {
+        joint_type: JointType::Rope as u32,
+        body1: rb1_handle,
+        body2: rb2_handle,
+        local_anchor1: vector![local_anchor1_x, local_anchor1_y, local_anchor1_z],
+        local_anchor2: vector![local_anchor2_x, local_anchor2_y, local_anchor2_z],
+        limit_max: max_distance,
+        contacts_enabled: self_collision.into(),
+        ..Default::default()
+    })
 }
 
 #[unsafe(no_mangle)]
 extern "C" fn remove_joint(handle: SerializableImpulseJointHandle) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     psd.impulse_joint_set.remove(handle.into(), true);
+    psd.joint_break_thresholds.remove(&handle.into());
 }
 
 #[unsafe(no_mangle)]
 extern "C" fn get_transform(rb_handle: SerializableRigidBodyHandle) -> RapierTransform {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return (&Isometry3::identity()).into();
+    };
     let rb = psd.rigid_body_set.get(rb_handle.into()).unwrap();
-    let pos = rb.position();
-    RapierTransform {
-        rotation: pos.rotation.coords,
-        position: pos.translation.vector,
-    }
+    rb.position().into()
 }
 
 #[unsafe(no_mangle)]
@@ -473,7 +555,9 @@ extern "C" fn set_transform_position(
     position_y: f32,
     position_z: f32,
 ) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     let rb = psd.rigid_body_set.get_mut(rb_handle.into()).unwrap();
     let iso = Isometry::from_parts(
         Translation::from(vector![position_x, position_y, position_z]),
@@ -491,7 +575,9 @@ extern "C" fn set_transform_rotation(
     rotation_z: f32,
     rotation_w: f32,
 ) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     let rb: &mut RigidBody = psd.rigid_body_set.get_mut(rb_handle.into()).unwrap();
     rb.set_next_kinematic_rotation(UnitQuaternion::new_normalize(Quaternion::new(
         rotation_w, rotation_x, rotation_y, rotation_z,
@@ -509,7 +595,9 @@ extern "C" fn set_transform(
     rotation_z: f32,
     rotation_w: f32,
 ) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     let rb = psd.rigid_body_set.get_mut(rb_handle.into()).unwrap();
     let iso = Isometry::from_parts(
         Translation::from(vector![position_x, position_y, position_z]),
@@ -527,7 +615,9 @@ extern "C" fn set_linear_velocity(
     velocity_y: f32,
     velocity_z: f32,
 ) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     let rb = psd.rigid_body_set.get_mut(rb_handle.into()).unwrap();
     rb.set_linvel(vector![velocity_x, velocity_y, velocity_z], true);
 }
@@ -539,23 +629,29 @@ extern "C" fn set_angular_velocity(
     velocity_y: f32,
     velocity_z: f32,
 ) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     let rb = psd.rigid_body_set.get_mut(rb_handle.into()).unwrap();
     rb.set_angvel(vector![velocity_x, velocity_y, velocity_z], true);
 }
 
 #[unsafe(no_mangle)]
 extern "C" fn get_linear_velocity(rb_handle: SerializableRigidBodyHandle) -> Vector3<f32> {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return Vector3::zeros();
+    };
     let rb = psd.rigid_body_set.get(rb_handle.into()).unwrap();
-    rb.linvel().clone()
+    *rb.linvel()
 }
 
 #[unsafe(no_mangle)]
 extern "C" fn get_angular_velocity(rb_handle: SerializableRigidBodyHandle) -> Vector3<f32> {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return Vector3::zeros();
+    };
     let rb = psd.rigid_body_set.get(rb_handle.into()).unwrap();
-    rb.angvel().clone()
+    *rb.angvel()
 }
 
 // Add Force
@@ -567,24 +663,13 @@ extern "C" fn add_force(
     force_z: f32,
     mode: ForceMode,
 ) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     let rb = psd.rigid_body_set.get_mut(rb_handle.into()).unwrap();
-    let mut linvel = rb.linvel().clone();
-    match mode {
-        ForceMode::Force => {
-            linvel +=
-                vector![force_x, force_y, force_z] * psd.integration_parameters.dt / rb.mass();
-        }
-        ForceMode::Impulse => {
-            linvel += vector![force_x, force_y, force_z] / rb.mass();
-        }
-        ForceMode::VelocityChange => {
-            linvel += vector![force_x, force_y, force_z];
-        }
-        ForceMode::Acceleration => {
-            linvel += vector![force_x, force_y, force_z] * psd.integration_parameters.dt;
-        }
-    }
+    let dt = psd.integration_parameters.dt;
+    let linvel =
+        *rb.linvel() + mode.velocity_change(vector![force_x, force_y, force_z], rb.mass(), dt);
     // log::info!("linvel: {:?}, mode: {:?}", linvel, mode);
     rb.set_linvel(linvel, true);
 }
@@ -597,24 +682,13 @@ extern "C" fn add_torque(
     torque_z: f32,
     mode: ForceMode,
 ) {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
     let rb = psd.rigid_body_set.get_mut(rb_handle.into()).unwrap();
-    let mut angvel = rb.angvel().clone();
-    match mode {
-        ForceMode::Force => {
-            angvel +=
-                vector![torque_x, torque_y, torque_z] * psd.integration_parameters.dt / rb.mass();
-        }
-        ForceMode::Impulse => {
-            angvel += vector![torque_x, torque_y, torque_z] / rb.mass();
-        }
-        ForceMode::VelocityChange => {
-            angvel += vector![torque_x, torque_y, torque_z];
-        }
-        ForceMode::Acceleration => {
-            angvel += vector![torque_x, torque_y, torque_z] * psd.integration_parameters.dt;
-        }
-    }
+    let dt = psd.integration_parameters.dt;
+    let angvel =
+        *rb.angvel() + mode.velocity_change(vector![torque_x, torque_y, torque_z], rb.mass(), dt);
     rb.set_angvel(angvel, true);
 }
 
@@ -640,27 +714,25 @@ pub extern "C" fn set_integration_parameters(
     // Length unit
     length_unit: f32,
 ) {
-    use std::num::NonZeroUsize;
-
-    let psd = get_mutable_physics_solver();
-    psd.integration_parameters.dt = dt;
-    psd.integration_parameters.min_ccd_dt = dt / 100.0;
-    psd.integration_parameters.num_solver_iterations =
-        NonZeroUsize::new(solver_iterations as usize).unwrap_or(NonZeroUsize::new(4).unwrap());
-    psd.integration_parameters.num_internal_pgs_iterations = solver_pgs_iterations;
-    psd.integration_parameters
-        .num_additional_friction_iterations = solver_additional_friction_iterations;
-    psd.integration_parameters
-        .num_internal_stabilization_iterations = solver_stabilization_iterations;
-    psd.integration_parameters.max_ccd_substeps = ccd_substeps;
-    psd.integration_parameters.contact_damping_ratio = contact_damping_ratio;
-    psd.integration_parameters.joint_damping_ratio = joint_damping_ratio;
-    psd.integration_parameters.contact_natural_frequency = contact_frequency;
-    psd.integration_parameters.joint_natural_frequency = joint_frequency;
-    psd.integration_parameters.normalized_prediction_distance = prediction_distance;
-    psd.integration_parameters
-        .normalized_max_corrective_velocity = max_corrective_velocity;
-    psd.integration_parameters.length_unit = length_unit;
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.apply_integration_parameters(&IntegrationParametersDescriptor {
+        dt,
+        solver_iterations,
+        solver_pgs_iterations,
+        solver_additional_friction_iterations,
+        solver_stabilization_iterations,
+        ccd_substeps,
+        contact_damping_ratio,
+        joint_damping_ratio,
+        contact_frequency,
+        joint_frequency,
+        prediction_distance,
+        max_corrective_velocity,
+        length_unit,
+        ..Default::default()
+    });
 }
 
 // Scene Query
@@ -685,7 +757,9 @@ extern "C" fn cast_ray(
     dir_z: f32,
     out_hit: *mut RaycastHit,
 ) -> bool {
-    let psd = get_mutable_physics_solver();
+    let Some(psd) = get_mutable_physics_solver() else {
+        return false;
+    };
     let ray = Ray::new(point![from_x, from_y, from_z], vector![dir_x, dir_y, dir_z]);
     if let Some((handle, intersection)) = psd.query_pipeline.cast_ray_and_get_normal(
         &psd.rigid_body_set,
@@ -731,6 +805,18 @@ pub enum ForceMode {
     Acceleration = 5,
 }
 
+impl ForceMode {
+    /// Velocity change caused by applying `value` for one step of `dt` in this mode.
+    fn velocity_change(self, value: Vector<Real>, mass: Real, dt: Real) -> Vector<Real> {
+        match self {
+            ForceMode::Force => value * dt / mass,
+            ForceMode::Impulse => value / mass,
+            ForceMode::VelocityChange => value,
+            ForceMode::Acceleration => value * dt,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 struct RapierTransform {
@@ -738,8 +824,17 @@ struct RapierTransform {
     position: Vector<f32>,
 }
 
+impl From<&Isometry3<Real>> for RapierTransform {
+    fn from(pos: &Isometry3<Real>) -> Self {
+        RapierTransform {
+            rotation: pos.rotation.coords,
+            position: pos.translation.vector,
+        }
+    }
+}
+
 // PhysicsSolverData is a struct that holds all the data needed to solve physics.
-pub struct PhysicsSolverData<'a> {
+pub struct PhysicsSolverData {
     pub gravity: Vector<f32>,
     pub integration_parameters: IntegrationParameters,
     pub physics_pipeline: PhysicsPipeline,
@@ -750,18 +845,55 @@ pub struct PhysicsSolverData<'a> {
     pub multibody_joint_set: MultibodyJointSet,
     pub ccd_solver: CCDSolver,
     pub query_pipeline: QueryPipeline,
-    pub physics_hooks: &'a dyn PhysicsHooks,
-    pub event_handler: &'a dyn EventHandler,
 
     pub rigid_body_set: RigidBodySet,
     pub collider_set: ColliderSet,
-}
 
-impl Default for PhysicsSolverData<'_> {
+    // Pose of the bodies moved by the last step before it ran, used to interpolate between physics frames.
+    pub previous_poses: Vec<PreviousPose>,
+    // Number of steps run so far, tells which entries of `previous_poses` belong to the last step.
+    pub step_count: u64,
+    // Unsimulated time carried over between frames by the fixed-timestep accumulator.
+    pub time_accumulator: f32,
+    // Bodies that moved during the last step, so only those need syncing back to Unity.
+    pub moved_bodies: Vec<SerializableRigidBodyHandle>,
+    // Optional double-buffered pose array shared with C#, written at the end of every step.
+    pub transform_stream: Option<TransformStream>,
+    // Joints that get removed once their impulse goes over a threshold.
+    pub joint_break_thresholds: HashMap<ImpulseJointHandle, JointBreakThreshold>,
+    // Joints that broke during the last step.
+    pub broken_joints: Vec<SerializableJointBrokenEvent>,
+    // Contact points of the collision events returned by the last step.
+    pub collision_contacts: Vec<SerializableContactPoint>,
+    // Contact force events of the last step.
+    pub contact_force_events: Vec<SerializableContactForceEvent>,
+    // Pairs of colliders currently touching or intersecting, in `pair_key` order.
+    pub active_pairs: BTreeSet<PairKey>,
+    // Stay events of the last step, and the colliders that opted out of them.
+    pub collision_stay_events: Vec<SerializableCollisionEvent>,
+    pub stay_events_disabled: HashSet<ColliderHandle>,
+    // Whether stay events get their contact points gathered like started events.
+    pub stay_event_contacts: bool,
+    // Parent body and user id of colliders removed since the last step, still reported by their stop events.
+    pub removed_colliders: HashMap<ColliderHandle, (RigidBodyHandle, u64)>,
+    // Physics hooks passed to every step.
+    pub hooks: SolverHooks,
+    // Raw events written by the last step, drained into the serializable buffers once it is done.
+    pub event_buffers: EventBuffers,
+    // Collision events of the last step, and the array `solve` hands out for them.
+    pub collision_events: Vec<SerializableCollisionEvent>,
+    pub(crate) collision_events_view: RawArray<SerializableCollisionEvent>,
+    // Pairs that started touching during the last step, so they don't also get a stay event.
+    pub started_pairs: HashSet<PairKey>,
+}
+
+impl Default for PhysicsSolverData {
     fn default() -> Self {
-        let mut integration_parameters = IntegrationParameters::default();
-        integration_parameters.dt = 1.0 / 50.0;
-        integration_parameters.min_ccd_dt = 1.0 / 50.0 / 100.0;
+        let integration_parameters = IntegrationParameters {
+            dt: 1.0 / 50.0,
+            min_ccd_dt: 1.0 / 50.0 / 100.0,
+            ..IntegrationParameters::default()
+        };
         PhysicsSolverData {
             gravity: vector![0.0, -9.81, 0.0],
             integration_parameters,
@@ -773,29 +905,68 @@ impl Default for PhysicsSolverData<'_> {
             multibody_joint_set: MultibodyJointSet::new(),
             ccd_solver: CCDSolver::new(),
             query_pipeline: QueryPipeline::new(),
-            physics_hooks: &(),
-            event_handler: &(),
 
             rigid_body_set: RigidBodySet::new(),
             collider_set: ColliderSet::new(),
+
+            previous_poses: Vec::new(),
+            step_count: 0,
+            time_accumulator: 0.0,
+            moved_bodies: Vec::new(),
+            transform_stream: None,
+            joint_break_thresholds: HashMap::new(),
+            broken_joints: Vec::new(),
+            collision_contacts: Vec::new(),
+            contact_force_events: Vec::new(),
+            active_pairs: BTreeSet::new(),
+            collision_stay_events: Vec::new(),
+            stay_events_disabled: HashSet::new(),
+            stay_event_contacts: false,
+            removed_colliders: HashMap::new(),
+            hooks: SolverHooks::default(),
+            event_buffers: EventBuffers::default(),
+            collision_events: Vec::new(),
+            collision_events_view: RawArray {
+                ptr: std::ptr::null_mut(),
+                len: 0,
+                capacity: 0,
+            },
+            started_pairs: HashSet::new(),
         }
     }
 }
 
-#[repr(C)]
-#[derive(Debug, Clone, Copy)]
-struct SerializableCollisionEvent {
-    collider1: SerializableColliderHandle,
-    collider2: SerializableColliderHandle,
-    is_started: bool,
-}
-
-impl PhysicsSolverData<'_> {
-    fn solve(&mut self) -> Vec<SerializableCollisionEvent> {
-        let (collision_send, collision_recv) = crossbeam::channel::unbounded();
-        let (contact_force_send, _contact_force_recv) = crossbeam::channel::unbounded();
-        let event_handler = ChannelEventCollector::new(collision_send, contact_force_send);
+impl PhysicsSolverData {
+    /// Removes a body with its colliders and joints, along with any state we keep for it.
+    /// Returns false if the handle was already invalid.
+    fn remove_rigid_body(&mut self, handle: RigidBodyHandle) -> bool {
+        self.remember_removed_colliders(handle);
+        let colliders = self
+            .rigid_body_set
+            .get(handle)
+            .map(|rb| rb.colliders().to_vec())
+            .unwrap_or_default();
+        let removed = self.rigid_body_set.remove(
+            handle,
+            &mut self.island_manager,
+            &mut self.collider_set,
+            &mut self.impulse_joint_set,
+            &mut self.multibody_joint_set,
+            true,
+        );
+        for collider in colliders {
+            self.forget_collider_hooks(collider);
+        }
+        if let Some(stream) = self.transform_stream.as_mut() {
+            stream.unregister(handle);
+        }
+        removed.is_some()
+    }
 
+    fn solve(&mut self) {
+        self.store_previous_poses();
+        self.begin_moved_bodies();
+        STEPPING.store(true, Ordering::Relaxed);
         self.physics_pipeline.step(
             &self.gravity,
             &self.integration_parameters,
@@ -808,29 +979,16 @@ impl PhysicsSolverData<'_> {
             &mut self.multibody_joint_set,
             &mut self.ccd_solver,
             Some(&mut self.query_pipeline),
-            &(),
-            &event_handler,
+            &self.hooks,
+            &self.event_buffers,
         );
+        STEPPING.store(false, Ordering::Relaxed);
 
-        let mut collision_events = Vec::new();
-        while let Ok(collision_event) = collision_recv.try_recv() {
-            if collision_event.started() {
-                collision_events.push(SerializableCollisionEvent {
-                    collider1: collision_event.collider1().into(),
-                    collider2: collision_event.collider2().into(),
-                    is_started: true,
-                });
-            } else if collision_event.stopped() {
-                collision_events.push(SerializableCollisionEvent {
-                    collider1: collision_event.collider1().into(),
-                    collider2: collision_event.collider2().into(),
-                    is_started: false,
-                });
-            } else {
-                log::warn!("Unknown collision event: {:?}", collision_event);
-            }
-        }
+        self.end_moved_bodies();
+        self.break_joints();
+        self.write_transform_stream();
 
-        collision_events
+        self.collect_contact_force_events();
+        self.collect_collision_events();
     }
 }
@@ -0,0 +1,454 @@
+// Reduced-coordinate articulations, the counterpart of Unity's `ArticulationBody`.
+// Each joint links a child body to its parent, the chain stays exact however long it gets
+// because the solver works on the joint coordinates instead of constraining free bodies.
+
+use crate::descriptors::{
+    Descriptor, GenericJointDescriptor, JointDescriptor, field_end, isometry_from_parts,
+    latest_version, read_descriptor,
+};
+use crate::handles::{
+    SerializableJointAxis, SerializableMotorModel, SerializableMultibodyJointHandle,
+};
+use crate::{PhysicsSolverData, get_mutable_physics_solver};
+use rapier3d::na::{DVector, Vector3, Vector4};
+use rapier3d::prelude::*;
+
+impl PhysicsSolverData {
+    fn insert_multibody_joint(
+        &mut self,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        joint: GenericJoint,
+    ) -> SerializableMultibodyJointHandle {
+        // Fails if body2 already has a parent link or if the joint would close a loop
+        let Some(handle) = self.multibody_joint_set.insert(body1, body2, joint, true) else {
+            log::warn!("Failed to create multibody joint, the bodies can't be linked");
+            return MultibodyJointHandle::invalid().into();
+        };
+        handle.into()
+    }
+
+    /// Joint data of a multibody link, waking its body so the change is simulated.
+    fn multibody_joint_data_mut(
+        &mut self,
+        handle: MultibodyJointHandle,
+    ) -> Option<&mut GenericJoint> {
+        let (multibody, link_id) = self.multibody_joint_set.get_mut(handle)?;
+        let link = multibody.link_mut(link_id)?;
+        if let Some(rb) = self.rigid_body_set.get_mut(link.rigid_body_handle()) {
+            rb.wake_up(true);
+        }
+        Some(&mut link.joint.data)
+    }
+}
+
+/// Links `body2` to its parent `body1` with the joint described by `descriptor`.
+/// `body2` must not already have a parent link.
+#[unsafe(no_mangle)]
+extern "C" fn add_multibody_joint_from_descriptor(
+    descriptor: *const JointDescriptor,
+) -> SerializableMultibodyJointHandle {
+    let Some(descriptor) = (unsafe { read_descriptor(descriptor) }) else {
+        log::warn!(
+            "add_multibody_joint_from_descriptor called with a null or unversioned descriptor"
+        );
+        return MultibodyJointHandle::invalid().into();
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return MultibodyJointHandle::invalid().into();
+    };
+    let Some(joint) = psd.build_joint(&descriptor) else {
+        return MultibodyJointHandle::invalid().into();
+    };
+    psd.insert_multibody_joint(descriptor.body1.into(), descriptor.body2.into(), joint)
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn add_multibody_generic_joint(
+    descriptor: *const GenericJointDescriptor,
+) -> SerializableMultibodyJointHandle {
+    let Some(descriptor) = (unsafe { read_descriptor(descriptor) }) else {
+        log::warn!("add_multibody_generic_joint called with a null or unversioned descriptor");
+        return MultibodyJointHandle::invalid().into();
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return MultibodyJointHandle::invalid().into();
+    };
+    let body1: RigidBodyHandle = descriptor.body1.into();
+    let body2: RigidBodyHandle = descriptor.body2.into();
+    if !psd.rigid_body_set.contains(body1) || !psd.rigid_body_set.contains(body2) {
+        log::warn!("Failed to create generic multibody joint, invalid rigid body handle");
+        return MultibodyJointHandle::invalid().into();
+    }
+    let Some(joint) = descriptor.build() else {
+        return MultibodyJointHandle::invalid().into();
+    };
+    psd.insert_multibody_joint(body1, body2, joint)
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn remove_multibody_joint(handle: SerializableMultibodyJointHandle) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.multibody_joint_set.remove(handle.into(), true);
+}
+
+/// Same as `set_joint_motor`, for a multibody joint.
+#[unsafe(no_mangle)]
+extern "C" fn set_multibody_joint_motor(
+    handle: SerializableMultibodyJointHandle,
+    axis: u32,
+    target_pos: f32,
+    target_vel: f32,
+    stiffness: f32,
+    damping: f32,
+    max_force: f32,
+    model: u32,
+) {
+    let (Ok(axis), Ok(model)) = (
+        SerializableJointAxis::try_from(axis),
+        SerializableMotorModel::try_from(model),
+    ) else {
+        log::warn!(
+            "set_multibody_joint_motor: invalid axis {} or motor model {}",
+            axis,
+            model
+        );
+        return;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.multibody_joint_data_mut(handle.into()) else {
+        log::warn!(
+            "set_multibody_joint_motor: invalid joint handle {:?}",
+            handle
+        );
+        return;
+    };
+    let axis: JointAxis = axis.into();
+    joint
+        .set_motor(axis, target_pos, target_vel, stiffness, damping)
+        .set_motor_max_force(axis, max_force)
+        .set_motor_model(axis, model.into());
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn disable_multibody_joint_motor(handle: SerializableMultibodyJointHandle, axis: u32) {
+    let Ok(axis) = SerializableJointAxis::try_from(axis) else {
+        log::warn!("disable_multibody_joint_motor: invalid axis {}", axis);
+        return;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.multibody_joint_data_mut(handle.into()) else {
+        log::warn!(
+            "disable_multibody_joint_motor: invalid joint handle {:?}",
+            handle
+        );
+        return;
+    };
+    let axis: JointAxis = axis.into();
+    joint.motor_axes.remove(axis.into());
+    joint.motors[axis as usize] = JointMotor::default();
+}
+
+/// Same as `set_joint_limits`, for a multibody joint.
+#[unsafe(no_mangle)]
+extern "C" fn set_multibody_joint_limits(
+    handle: SerializableMultibodyJointHandle,
+    axis: u32,
+    min: f32,
+    max: f32,
+) {
+    let Ok(axis) = SerializableJointAxis::try_from(axis) else {
+        log::warn!("set_multibody_joint_limits: invalid axis {}", axis);
+        return;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    let Some(joint) = psd.multibody_joint_data_mut(handle.into()) else {
+        log::warn!(
+            "set_multibody_joint_limits: invalid joint handle {:?}",
+            handle
+        );
+        return;
+    };
+    joint.set_limits(axis.into(), [min, max]);
+}
+
+// Generalized coordinates
+
+/// Generalized positions of a joint paired with their axis, one per free axis in `LinX` to `AngZ` order.
+fn joint_coordinates(joint: &MultibodyJoint) -> impl Iterator<Item = (usize, Real)> {
+    let data = &joint.data;
+    // Pose of the second frame in the first, made of the joint's translation and rotation coordinates
+    let relative = data.local_frame1.inverse() * joint.body_to_parent() * data.local_frame2;
+    let rotation_vector = relative.rotation.scaled_axis();
+    let locked_axes = data.locked_axes.bits();
+    (0..SPATIAL_DIM)
+        .filter(move |axis| locked_axes & (1 << axis) == 0)
+        .map(move |axis| {
+            let position = if axis < DIM {
+                relative.translation.vector[axis]
+            } else {
+                rotation_vector[axis - DIM]
+            };
+            (axis, position)
+        })
+}
+
+/// Number of degrees of freedom of the joint, the length of its generalized coordinates.
+#[unsafe(no_mangle)]
+extern "C" fn get_multibody_joint_dof_count(handle: SerializableMultibodyJointHandle) -> usize {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return 0;
+    };
+    let Some((multibody, link_id)) = psd.multibody_joint_set.get(handle.into()) else {
+        log::warn!(
+            "get_multibody_joint_dof_count: invalid joint handle {:?}",
+            handle
+        );
+        return 0;
+    };
+    multibody.link(link_id).map_or(0, |link| link.joint.ndofs())
+}
+
+/// Reads the generalized positions and velocities of the joint, one per free axis in `LinX` to `AngZ` order.
+/// Linear coordinates are distances along the axis, angular ones are the components of the rotation
+/// vector in radians, which is the joint angle when there is a single angular axis.
+/// Writes at most `capacity` values into each buffer that isn't null and returns the number of degrees of freedom.
+#[unsafe(no_mangle)]
+extern "C" fn get_multibody_joint_coordinates(
+    handle: SerializableMultibodyJointHandle,
+    out_positions: *mut f32,
+    out_velocities: *mut f32,
+    capacity: usize,
+) -> usize {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return 0;
+    };
+    let Some((multibody, link)) = psd
+        .multibody_joint_set
+        .get(handle.into())
+        .and_then(|(multibody, link_id)| Some((multibody, multibody.link(link_id)?)))
+    else {
+        log::warn!(
+            "get_multibody_joint_coordinates: invalid joint handle {:?}",
+            handle
+        );
+        return 0;
+    };
+
+    let velocities = multibody.joint_velocity(link);
+    for (dof, (_, position)) in joint_coordinates(&link.joint).enumerate().take(capacity) {
+        unsafe {
+            if !out_positions.is_null() {
+                *out_positions.add(dof) = position;
+            }
+            if !out_velocities.is_null() {
+                *out_velocities.add(dof) = velocities[dof];
+            }
+        }
+    }
+    link.joint.ndofs()
+}
+
+// Inverse kinematics
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct InverseKinematicsDescriptor {
+    pub version: u32,
+    // Higher values converge slower but overshoot less
+    pub damping: f32,
+    pub max_iterations: u32,
+    // Axes of the target pose to reach, bits 0 to 5 are LinX, LinY, LinZ, AngX, AngY and AngZ
+    pub constrained_axes: u32,
+    // Stop iterating once both errors are below these
+    pub epsilon_linear: f32,
+    pub epsilon_angular: f32,
+}
+
+impl Descriptor for InverseKinematicsDescriptor {
+    const VERSION_ENDS: &'static [usize] =
+        &[field_end!(InverseKinematicsDescriptor, epsilon_angular: f32)];
+}
+
+impl Default for InverseKinematicsDescriptor {
+    fn default() -> Self {
+        let options = InverseKinematicsOption::default();
+        InverseKinematicsDescriptor {
+            version: latest_version::<InverseKinematicsDescriptor>(),
+            damping: options.damping,
+            max_iterations: options.max_iters as u32,
+            constrained_axes: options.constrained_axes.bits() as u32,
+            epsilon_linear: options.epsilon_linear,
+            epsilon_angular: options.epsilon_angular,
+        }
+    }
+}
+
+impl From<&InverseKinematicsDescriptor> for InverseKinematicsOption {
+    fn from(value: &InverseKinematicsDescriptor) -> Self {
+        InverseKinematicsOption {
+            damping: value.damping,
+            max_iters: value.max_iterations as usize,
+            constrained_axes: JointAxesMask::from_bits_truncate(value.constrained_axes as u8),
+            epsilon_linear: value.epsilon_linear,
+            epsilon_angular: value.epsilon_angular,
+        }
+    }
+}
+
+/// What `solve_multibody_ik` does with the displacements it computed.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InverseKinematicsMode {
+    // Only write them to the output buffer
+    Displacements = 0,
+    // Move the whole articulation to the solved pose, like a teleport
+    ApplyPose = 1,
+    // Use the solved coordinates as the target position of every enabled motor,
+    // rejected when a motorized joint has more than one free angular axis
+    MotorTargets = 2,
+}
+
+impl TryFrom<u32> for InverseKinematicsMode {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(InverseKinematicsMode::Displacements),
+            1 => Ok(InverseKinematicsMode::ApplyPose),
+            2 => Ok(InverseKinematicsMode::MotorTargets),
+            _ => Err(()),
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn default_inverse_kinematics_descriptor() -> InverseKinematicsDescriptor {
+    InverseKinematicsDescriptor::default()
+}
+
+/// Whether the joint drives an angular axis while having more than one of them free.
+/// Rapier tracks such joints with accumulated angles that don't describe their rotation,
+/// so a solved pose can't be turned into motor targets for them.
+fn has_multi_angular_motor(joint: &MultibodyJoint) -> bool {
+    let free_angular_axes = JointAxesMask::ANG_AXES - joint.data.locked_axes;
+    free_angular_axes.bits().count_ones() > 1 && joint.data.motor_axes.intersects(free_angular_axes)
+}
+
+/// Solves for the joint displacements that bring the body of the `handle` link to the target pose,
+/// moving every joint between it and the root of its articulation.
+/// The displacements of the whole articulation are written to `out_displacements` when it isn't null,
+/// link after link in creation order, each ordered like `get_multibody_joint_coordinates`.
+/// A dynamic root comes first with its 6 free axes. Writes at most `capacity` values
+/// and returns the articulation's number of degrees of freedom.
+/// `mode` is an `InverseKinematicsMode`, `options` can be null to use the defaults.
+/// Returns 0 without solving if `mode` is unknown or can't be applied to the articulation.
+#[unsafe(no_mangle)]
+extern "C" fn solve_multibody_ik(
+    handle: SerializableMultibodyJointHandle,
+    target_position: Vector3<f32>,
+    target_rotation: Vector4<f32>,
+    options: *const InverseKinematicsDescriptor,
+    mode: u32,
+    out_displacements: *mut f32,
+    capacity: usize,
+) -> usize {
+    let options: InverseKinematicsOption =
+        (&unsafe { read_descriptor(options) }.unwrap_or_default()).into();
+    let Ok(mode) = InverseKinematicsMode::try_from(mode) else {
+        log::warn!("solve_multibody_ik: unknown mode {}", mode);
+        return 0;
+    };
+    let Some(psd) = get_mutable_physics_solver() else {
+        return 0;
+    };
+    let Some(link_id) = psd
+        .multibody_joint_set
+        .get(handle.into())
+        .and_then(|(multibody, link_id)| multibody.link(link_id))
+        .and_then(|link| {
+            psd.multibody_joint_set
+                .rigid_body_link(link.rigid_body_handle())
+        })
+        .copied()
+    else {
+        log::warn!("solve_multibody_ik: invalid joint handle {:?}", handle);
+        return 0;
+    };
+    let multibody = psd
+        .multibody_joint_set
+        .get_multibody_mut(link_id.multibody)
+        .unwrap();
+
+    if mode == InverseKinematicsMode::MotorTargets
+        && multibody
+            .links()
+            .any(|link| has_multi_angular_motor(&link.joint))
+    {
+        log::warn!(
+            "solve_multibody_ik: motor targets need every motorized joint to have at most one free angular axis"
+        );
+        return 0;
+    }
+
+    // Syncs the root with its body, a link added since the last step still assumes a free root
+    multibody.forward_kinematics(&psd.rigid_body_set, true);
+
+    let target_pose = isometry_from_parts(target_position, target_rotation);
+    let mut displacements = DVector::zeros(multibody.ndofs());
+    multibody.inverse_kinematics(
+        &psd.rigid_body_set,
+        link_id.id,
+        &options,
+        &target_pose,
+        |_| true,
+        &mut displacements,
+    );
+
+    match mode {
+        InverseKinematicsMode::Displacements => {}
+        InverseKinematicsMode::ApplyPose => {
+            multibody.apply_displacements(displacements.as_slice());
+            multibody.forward_kinematics(&psd.rigid_body_set, false);
+            multibody.update_rigid_bodies(&mut psd.rigid_body_set, false);
+        }
+        InverseKinematicsMode::MotorTargets => {
+            // Links store their coordinates one after the other, in link order
+            let mut offset = 0;
+            for link in multibody.links_mut() {
+                let coordinates: Vec<_> = joint_coordinates(&link.joint).collect();
+                let data = &mut link.joint.data;
+                for (dof, (axis, position)) in coordinates.into_iter().enumerate() {
+                    if data.motor_axes.bits() & (1 << axis) != 0 {
+                        data.motors[axis].target_pos = position + displacements[offset + dof];
+                    }
+                }
+                offset += link.joint.ndofs();
+            }
+        }
+    }
+    if mode != InverseKinematicsMode::Displacements {
+        for link in multibody.links() {
+            if let Some(rb) = psd.rigid_body_set.get_mut(link.rigid_body_handle()) {
+                rb.wake_up(true);
+            }
+        }
+    }
+
+    if !out_displacements.is_null() {
+        for (i, displacement) in displacements.iter().take(capacity).enumerate() {
+            unsafe {
+                *out_displacements.add(i) = *displacement;
+            }
+        }
+    }
+    displacements.len()
+}
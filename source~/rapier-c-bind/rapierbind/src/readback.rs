@@ -0,0 +1,166 @@
+use crate::handles::SerializableRigidBodyHandle;
+use crate::{PhysicsSolverData, RapierTransform, RawArray, get_mutable_physics_solver};
+use rapier3d::na::Vector3;
+use rapier3d::prelude::*;
+
+/// Awake dynamic and kinematic bodies, the only ones the solver can move.
+/// Takes the island manager rather than the solver so callers can keep mutating their other fields.
+pub(crate) fn active_bodies(
+    island_manager: &IslandManager,
+) -> impl Iterator<Item = &RigidBodyHandle> {
+    island_manager
+        .active_dynamic_bodies()
+        .iter()
+        .chain(island_manager.active_kinematic_bodies())
+}
+
+impl PhysicsSolverData {
+    /// Called before stepping, remembers which bodies are awake going into the step.
+    pub(crate) fn begin_moved_bodies(&mut self) {
+        self.moved_bodies.clear();
+        self.push_active_bodies_as_moved();
+    }
+
+    /// Called after stepping, turns the list from `begin_moved_bodies` into every body that moved.
+    /// Bodies that fell asleep during the step still made a last move, so they are kept,
+    /// the ones still awake are added back with the rest of the active set.
+    pub(crate) fn end_moved_bodies(&mut self) {
+        let bodies = &self.rigid_body_set;
+        self.moved_bodies.retain(|handle| {
+            bodies
+                .get((*handle).into())
+                .is_some_and(|rb| rb.is_sleeping())
+        });
+        self.push_active_bodies_as_moved();
+    }
+
+    fn push_active_bodies_as_moved(&mut self) {
+        self.moved_bodies.extend(
+            active_bodies(&self.island_manager)
+                .map(|handle| SerializableRigidBodyHandle::from(*handle)),
+        );
+    }
+}
+
+/// Writes the state of `rb` into slot `i` of every output buffer that isn't null.
+unsafe fn write_body_state(
+    rb: &RigidBody,
+    i: usize,
+    out_transforms: *mut RapierTransform,
+    out_linear_velocities: *mut Vector3<f32>,
+    out_angular_velocities: *mut Vector3<f32>,
+) {
+    unsafe {
+        if !out_transforms.is_null() {
+            *out_transforms.add(i) = rb.position().into();
+        }
+        if !out_linear_velocities.is_null() {
+            *out_linear_velocities.add(i) = *rb.linvel();
+        }
+        if !out_angular_velocities.is_null() {
+            *out_angular_velocities.add(i) = *rb.angvel();
+        }
+    }
+}
+
+/// Reads back the transforms of `handles_count` bodies in one call.
+/// Every output buffer must hold `handles_count` elements, any of them can be null to skip it.
+#[unsafe(no_mangle)]
+extern "C" fn get_transforms(
+    handles_ptr: *const SerializableRigidBodyHandle,
+    handles_count: usize,
+    out_transforms: *mut RapierTransform,
+    out_linear_velocities: *mut Vector3<f32>,
+    out_angular_velocities: *mut Vector3<f32>,
+) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    if handles_ptr.is_null() {
+        if handles_count > 0 {
+            log::warn!(
+                "get_transforms: null handles with a count of {}",
+                handles_count
+            );
+        }
+        return;
+    }
+    let handles = unsafe { std::slice::from_raw_parts(handles_ptr, handles_count) };
+    for (i, handle) in handles.iter().enumerate() {
+        if let Some(rb) = psd.rigid_body_set.get((*handle).into()) {
+            unsafe {
+                write_body_state(
+                    rb,
+                    i,
+                    out_transforms,
+                    out_linear_velocities,
+                    out_angular_velocities,
+                );
+            }
+        } else {
+            log::warn!("get_transforms: invalid rigid body handle {:?}", handle);
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn get_active_body_count() -> usize {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return 0;
+    };
+    active_bodies(&psd.island_manager).count()
+}
+
+/// Reads back the handles and transforms of every awake dynamic and kinematic body.
+/// Writes at most `capacity` entries and returns the number of active bodies,
+/// so a return value above `capacity` means the buffers were too small.
+/// Any output buffer can be null to skip it, including the handles.
+#[unsafe(no_mangle)]
+extern "C" fn get_active_transforms(
+    out_handles: *mut SerializableRigidBodyHandle,
+    out_transforms: *mut RapierTransform,
+    out_linear_velocities: *mut Vector3<f32>,
+    out_angular_velocities: *mut Vector3<f32>,
+    capacity: usize,
+) -> usize {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return 0;
+    };
+    let mut count = 0;
+    for handle in active_bodies(&psd.island_manager) {
+        let Some(rb) = psd.rigid_body_set.get(*handle) else {
+            continue;
+        };
+        if count < capacity {
+            unsafe {
+                if !out_handles.is_null() {
+                    *out_handles.add(count) = (*handle).into();
+                }
+                write_body_state(
+                    rb,
+                    count,
+                    out_transforms,
+                    out_linear_velocities,
+                    out_angular_velocities,
+                );
+            }
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Handles of every body that moved during the last `solve`.
+/// Borrowed from the solver, see `RawArray`.
+#[unsafe(no_mangle)]
+extern "C" fn get_moved_bodies() -> RawArray<SerializableRigidBodyHandle> {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return RawArray::empty();
+    };
+    let moved_bodies = &mut psd.moved_bodies;
+    RawArray {
+        ptr: moved_bodies.as_mut_ptr(),
+        len: moved_bodies.len(),
+        capacity: moved_bodies.capacity(),
+    }
+}
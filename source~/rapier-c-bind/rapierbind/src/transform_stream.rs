@@ -0,0 +1,143 @@
+use crate::handles::SerializableRigidBodyHandle;
+use crate::{PhysicsSolverData, RapierTransform, get_mutable_physics_solver};
+use rapier3d::na::Isometry3;
+use rapier3d::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// Shared with C#, which maps both buffers as NativeArrays and reads `front_index` to pick one.
+/// The header is boxed so its address stays valid for as long as the stream exists.
+#[repr(C)]
+pub struct TransformStreamHeader {
+    buffers: [*mut RapierTransform; 2],
+    capacity: usize,
+    // Buffer holding the poses of the last completed step
+    front_index: AtomicU32,
+    // Incremented after every flip so readers can tell a new step has been published
+    frame: AtomicU64,
+}
+
+// Persistent double-buffered array of transforms, `solve` writes the back buffer then flips.
+pub struct TransformStream {
+    header: Box<TransformStreamHeader>,
+    buffers: [Vec<RapierTransform>; 2],
+    slots: Vec<Option<RigidBodyHandle>>,
+    body_slots: HashMap<RigidBodyHandle, u32>,
+    free_slots: Vec<u32>,
+}
+
+impl TransformStream {
+    fn new(capacity: usize) -> Self {
+        let identity: RapierTransform = (&Isometry3::identity()).into();
+        let mut buffers = [vec![identity; capacity], vec![identity; capacity]];
+        let header = Box::new(TransformStreamHeader {
+            buffers: [buffers[0].as_mut_ptr(), buffers[1].as_mut_ptr()],
+            capacity,
+            front_index: AtomicU32::new(0),
+            frame: AtomicU64::new(0),
+        });
+        TransformStream {
+            header,
+            buffers,
+            slots: vec![None; capacity],
+            body_slots: HashMap::new(),
+            free_slots: (0..capacity as u32).rev().collect(),
+        }
+    }
+
+    fn register(&mut self, handle: RigidBodyHandle, pose: &Isometry3<Real>) -> u32 {
+        if let Some(slot) = self.body_slots.get(&handle) {
+            return *slot;
+        }
+        let Some(slot) = self.free_slots.pop() else {
+            log::warn!("Transform stream is full ({} bodies)", self.slots.len());
+            return u32::MAX;
+        };
+        self.slots[slot as usize] = Some(handle);
+        self.body_slots.insert(handle, slot);
+        // Fill both buffers so the body doesn't read as identity until the next flip
+        for buffer in &mut self.buffers {
+            buffer[slot as usize] = pose.into();
+        }
+        slot
+    }
+
+    pub(crate) fn unregister(&mut self, handle: RigidBodyHandle) {
+        if let Some(slot) = self.body_slots.remove(&handle) {
+            self.slots[slot as usize] = None;
+            self.free_slots.push(slot);
+        }
+    }
+
+    fn write_and_flip(&mut self, bodies: &RigidBodySet) {
+        let back_index = 1 - self.header.front_index.load(Ordering::Relaxed) as usize;
+        let back_buffer = &mut self.buffers[back_index];
+        for (slot, handle) in self.slots.iter().enumerate() {
+            if let Some(rb) = handle.and_then(|handle| bodies.get(handle)) {
+                back_buffer[slot] = rb.position().into();
+            }
+        }
+        self.header
+            .front_index
+            .store(back_index as u32, Ordering::Release);
+        self.header.frame.fetch_add(1, Ordering::Release);
+    }
+}
+
+impl PhysicsSolverData {
+    pub(crate) fn write_transform_stream(&mut self) {
+        if let Some(stream) = self.transform_stream.as_mut() {
+            stream.write_and_flip(&self.rigid_body_set);
+        }
+    }
+}
+
+/// Allocates the transform stream with room for `capacity` bodies, replacing any previous one.
+/// The returned header stays valid until `destroy_transform_stream`, `create_transform_stream` or `teardown`.
+#[unsafe(no_mangle)]
+extern "C" fn create_transform_stream(capacity: usize) -> *const TransformStreamHeader {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return std::ptr::null();
+    };
+    let stream = psd.transform_stream.insert(TransformStream::new(capacity));
+    &*stream.header
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn destroy_transform_stream() {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    psd.transform_stream = None;
+}
+
+/// Adds a body to the transform stream and returns the slot its pose is written to,
+/// or `u32::MAX` if there is no stream, it is full or the handle is invalid.
+#[unsafe(no_mangle)]
+extern "C" fn register_transform_stream_body(rb_handle: SerializableRigidBodyHandle) -> u32 {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return u32::MAX;
+    };
+    let Some(stream) = psd.transform_stream.as_mut() else {
+        log::warn!("register_transform_stream_body called before create_transform_stream");
+        return u32::MAX;
+    };
+    let Some(rb) = psd.rigid_body_set.get(rb_handle.into()) else {
+        log::warn!(
+            "register_transform_stream_body: invalid rigid body handle {:?}",
+            rb_handle
+        );
+        return u32::MAX;
+    };
+    stream.register(rb_handle.into(), rb.position())
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn unregister_transform_stream_body(rb_handle: SerializableRigidBodyHandle) {
+    let Some(psd) = get_mutable_physics_solver() else {
+        return;
+    };
+    if let Some(stream) = psd.transform_stream.as_mut() {
+        stream.unregister(rb_handle.into());
+    }
+}
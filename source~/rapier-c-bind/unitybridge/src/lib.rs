@@ -25,7 +25,7 @@ pub struct UnityInterfaceGUID {
 }
 
 // IUnityLog (0x9E7507fA5B444D5D, 0x92FB979515EA83FC)
-const IUNITY_LOG_GUID: UnityInterfaceGUID =  UnityInterfaceGUID{m_guidhigh:0x9E7507fA5B444D5D_u64, m_guidlow:0x92FB979515EA83FC_u64};
+const IUNITY_LOG_GUID: UnityInterfaceGUID =  UnityInterfaceGUID{m_guidhigh:0x9E7507FA5B444D5D_u64, m_guidlow:0x92FB979515EA83FC_u64};
 #[repr(C,packed)]
 #[allow(non_snake_case)]
 pub struct IUnityLog